@@ -0,0 +1,320 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use hello_world::columns::ColumnMapping;
+use hello_world::contractor_clusters::ClusterOptions;
+use hello_world::contractor_names::ContractorAliases;
+use hello_world::reports::{
+    generate_reports, Dimension, JvAllocation, Normalization, RankingKey, RegionalOptions, ReliabilityMode, ReportKind, ReportOptions,
+    RiskTiers, SortOrder,
+};
+use hello_world::{Dataset, DuplicatePolicy, FundingYears, LoadOptions};
+use crate::{list_loaded_files, load_file, profile_dataset, suggest_contractor_aliases};
+
+const USAGE: &str = "\
+Usage:
+  hello_world                                  interactive menu
+  hello_world [--out-dir DIR] COMMAND...
+
+Commands (run in the order given):
+  load FILE... [OPTIONS]   add CSV files to the dataset
+      --years SPEC             funding years, e.g. 2021-2023 or 2018,2020,2024
+      --date-formats LIST      date formats tried in order, separated by ';'
+      --excel-dates            also accept Excel serial dates
+      --columns FILE           JSON column mapping file
+      --on-duplicate POLICY    first, last or merge (default: first)
+      --contractor-aliases FILE  CSV of Alias,Canonical contractor names
+  list                     list loaded files
+  profile                  data-quality profile of the loaded projects
+                           (writes data_profile.csv, data_profile_checks.csv and
+                           data_profile.json)
+  suggest-aliases          cluster similar contractor names into merge suggestions
+                           (writes contractor_alias_suggestions.csv; mark rows
+                           Approved=yes and pass it back with --contractor-aliases)
+      --min-similarity X       edit-distance similarity from 0 to 1 (default: 0.85)
+      --min-token-overlap X    share of the shorter name's words found in the
+                               longer, from 0 to 1 (default: 1)
+  report [--all]           generate all reports (the default)
+      --only LIST              only these reports (1-4, summary), e.g. 1,4,summary
+      --group-by LIST          Report 1 grouping columns, e.g. province,municipality
+                               (default: region,mainisland)
+      --delay-bands LIST       Report 1 delay thresholds in days (default: 30,90,180,365)
+      --normalization METHOD   Report 1 EfficiencyScore scaling: minmax, rank, zscore or
+                               winsorized (default: minmax)
+      --top N                  contractors in Report 2, 0 for all (default: 15)
+      --min-projects N         minimum projects per contractor (default: 5)
+      --sort-by KEY            cost, projects, reliability, delay or savings
+      --order ORDER            asc or desc (default: desc)
+      --jv-split MODE          credit joint ventures to their members: combined, equal
+                               or full (default: combined)
+      --reliability MODE       formula or percentile (default: formula)
+      --delay-horizon DAYS     formula: delay that zeroes the delay score (default: 730)
+      --savings-target RATIO   formula: savings/cost ratio for the full savings score
+                               (default: 0.1)
+      --delay-weight W         weight of the delay component (default: 1)
+      --savings-weight W       weight of the savings component (default: 1)
+      --risk-tiers L,M,H       lowest index for Low, Medium, High risk (default: 75,50,25)
+
+Options:
+  --out-dir DIR            directory for reports and rejected_rows.csv (default: .)
+  -h, --help               show this help
+
+Example:
+  hello_world load north.csv south.csv --years 2021-2023 report --all --out-dir out
+
+Exit codes: 0 success, 1 load or report failure, 2 invalid arguments";
+
+const EXIT_FAILURE: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+
+enum Command {
+    Load { files: Vec<String>, options: LoadOptions },
+    List,
+    Profile,
+    SuggestAliases(ClusterOptions),
+    Report(Vec<ReportKind>, ReportOptions),
+}
+
+struct Invocation {
+    commands: Vec<Command>,
+    out_dir: PathBuf,
+    help: bool,
+}
+
+pub fn run(args: &[String]) -> ExitCode {
+    let invocation = match parse(args) {
+        Ok(invocation) => invocation,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            eprintln!();
+            eprintln!("{}", USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    if invocation.help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    if let Err(e) = fs::create_dir_all(&invocation.out_dir) {
+        eprintln!("Error: cannot create {}: {}", invocation.out_dir.display(), e);
+        return ExitCode::from(EXIT_FAILURE);
+    }
+
+    let mut dataset = Dataset::default();
+    for command in &invocation.commands {
+        let result = match command {
+            Command::Load { files, options } => files
+                .iter()
+                .try_for_each(|file| load_file(&mut dataset, file, options, &invocation.out_dir)),
+            Command::List => {
+                list_loaded_files(&dataset);
+                Ok(())
+            }
+            Command::Profile => {
+                if dataset.projects().is_empty() {
+                    Err("No projects loaded; nothing to profile".into())
+                } else {
+                    profile_dataset(&dataset, &invocation.out_dir)
+                }
+            }
+            Command::SuggestAliases(options) => {
+                if dataset.projects().is_empty() {
+                    Err("No projects loaded; no contractor names to compare".into())
+                } else {
+                    suggest_contractor_aliases(&dataset, options, &invocation.out_dir)
+                }
+            }
+            Command::Report(kinds, options) => {
+                if dataset.projects().is_empty() {
+                    Err("No projects loaded; nothing to report".into())
+                } else {
+                    generate_reports(&dataset, &invocation.out_dir, kinds, options)
+                }
+            }
+        };
+        if let Err(e) = result {
+            eprintln!("Error: {}", e);
+            return ExitCode::from(EXIT_FAILURE);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn parse(args: &[String]) -> Result<Invocation, String> {
+    let mut invocation = Invocation {
+        commands: Vec::new(),
+        out_dir: PathBuf::from("."),
+        help: false,
+    };
+    let mut args = args.iter().peekable();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-h" | "--help" => invocation.help = true,
+            "--out-dir" => invocation.out_dir = PathBuf::from(value(&mut args, arg)?),
+            "load" => {
+                let mut files = Vec::new();
+                let mut options = LoadOptions::default();
+                while let Some(next) = args.next_if(|a| !is_command(a)) {
+                    match next.as_str() {
+                        "--years" => options.funding_years = FundingYears::parse(&value(&mut args, next)?)?,
+                        "--date-formats" => {
+                            options.date_formats = value(&mut args, next)?
+                                .split(';')
+                                .map(str::trim)
+                                .filter(|f| !f.is_empty())
+                                .map(str::to_string)
+                                .collect();
+                        }
+                        "--excel-dates" => options.excel_serial_dates = true,
+                        "--columns" => {
+                            let path = value(&mut args, next)?;
+                            options.column_mapping = ColumnMapping::from_json_file(&path).map_err(|e| e.to_string())?;
+                        }
+                        "--contractor-aliases" => {
+                            let path = value(&mut args, next)?;
+                            options.contractor_aliases =
+                                ContractorAliases::from_csv_file(&path).map_err(|e| e.to_string())?;
+                        }
+                        "--on-duplicate" => {
+                            let policy = value(&mut args, next)?;
+                            options.duplicate_policy = DuplicatePolicy::parse(&policy)
+                                .ok_or_else(|| format!("Unknown duplicate policy: {:?}", policy))?;
+                        }
+                        "--out-dir" => invocation.out_dir = PathBuf::from(value(&mut args, next)?),
+                        "-h" | "--help" => invocation.help = true,
+                        flag if flag.starts_with("--") => return Err(format!("Unknown load option: {}", flag)),
+                        file => files.push(file.to_string()),
+                    }
+                }
+                if files.is_empty() && !invocation.help {
+                    return Err("load needs at least one file".to_string());
+                }
+                invocation.commands.push(Command::Load { files, options });
+            }
+            "list" => invocation.commands.push(Command::List),
+            "profile" => invocation.commands.push(Command::Profile),
+            "suggest-aliases" => {
+                let mut options = ClusterOptions::default();
+                while let Some(next) = args.next_if(|a| !is_command(a)) {
+                    match next.as_str() {
+                        "--min-similarity" => options.min_similarity = fraction(&value(&mut args, next)?, next)?,
+                        "--min-token-overlap" => options.min_token_overlap = fraction(&value(&mut args, next)?, next)?,
+                        "--out-dir" => invocation.out_dir = PathBuf::from(value(&mut args, next)?),
+                        "-h" | "--help" => invocation.help = true,
+                        other => return Err(format!("Unknown suggest-aliases option: {}", other)),
+                    }
+                }
+                invocation.commands.push(Command::SuggestAliases(options));
+            }
+            "report" => {
+                let mut kinds = ReportKind::ALL.to_vec();
+                let mut options = ReportOptions::default();
+                while let Some(next) = args.next_if(|a| !is_command(a)) {
+                    match next.as_str() {
+                        "--all" => kinds = ReportKind::ALL.to_vec(),
+                        "--only" => {
+                            kinds = value(&mut args, next)?
+                                .split(',')
+                                .map(|k| ReportKind::parse(k).ok_or_else(|| format!("Unknown report: {:?}", k)))
+                                .collect::<Result<_, _>>()?;
+                        }
+                        "--group-by" => options.regional.group_by = Dimension::parse_list(&value(&mut args, next)?)?,
+                        "--delay-bands" => {
+                            options.regional.delay_bands = RegionalOptions::parse_bands(&value(&mut args, next)?)?
+                        }
+                        "--normalization" => {
+                            let method = value(&mut args, next)?;
+                            options.regional.normalization = Normalization::parse(&method)
+                                .ok_or_else(|| format!("Unknown normalization: {:?}", method))?;
+                        }
+                        "--top" => {
+                            let n = number(&value(&mut args, next)?, next)?;
+                            options.ranking.top_n = if n == 0 { None } else { Some(n) };
+                        }
+                        "--min-projects" => options.ranking.min_projects = number(&value(&mut args, next)?, next)?,
+                        "--sort-by" => {
+                            let key = value(&mut args, next)?;
+                            options.ranking.sort_key =
+                                RankingKey::parse(&key).ok_or_else(|| format!("Unknown sort key: {:?}", key))?;
+                        }
+                        "--order" => {
+                            let order = value(&mut args, next)?;
+                            options.ranking.order =
+                                SortOrder::parse(&order).ok_or_else(|| format!("Unknown order: {:?}", order))?;
+                        }
+                        "--jv-split" => {
+                            let mode = value(&mut args, next)?;
+                            options.ranking.jv_allocation = JvAllocation::parse(&mode)
+                                .ok_or_else(|| format!("Unknown JV allocation: {:?}", mode))?;
+                        }
+                        "--reliability" => {
+                            let mode = value(&mut args, next)?;
+                            options.ranking.reliability.mode = ReliabilityMode::parse(&mode)
+                                .ok_or_else(|| format!("Unknown reliability mode: {:?}", mode))?;
+                        }
+                        "--delay-horizon" => {
+                            let days = decimal(&value(&mut args, next)?, next)?;
+                            if days <= 0.0 {
+                                return Err(format!("{} must be greater than 0", next));
+                            }
+                            options.ranking.reliability.delay_horizon_days = days;
+                        }
+                        "--savings-target" => {
+                            let ratio = decimal(&value(&mut args, next)?, next)?;
+                            if ratio <= 0.0 {
+                                return Err(format!("{} must be greater than 0", next));
+                            }
+                            options.ranking.reliability.savings_target = ratio;
+                        }
+                        "--delay-weight" => {
+                            options.ranking.reliability.delay_weight = decimal(&value(&mut args, next)?, next)?
+                        }
+                        "--savings-weight" => {
+                            options.ranking.reliability.savings_weight = decimal(&value(&mut args, next)?, next)?
+                        }
+                        "--risk-tiers" => options.ranking.reliability.tiers = RiskTiers::parse(&value(&mut args, next)?)?,
+                        "--out-dir" => invocation.out_dir = PathBuf::from(value(&mut args, next)?),
+                        "-h" | "--help" => invocation.help = true,
+                        other => return Err(format!("Unknown report option: {}", other)),
+                    }
+                }
+                options.ranking.reliability.check_weights()?;
+                invocation.commands.push(Command::Report(kinds, options));
+            }
+            other => return Err(format!("Unknown command: {}", other)),
+        }
+    }
+
+    if invocation.commands.is_empty() && !invocation.help {
+        return Err("No command given".to_string());
+    }
+    Ok(invocation)
+}
+
+fn is_command(arg: &str) -> bool {
+    matches!(arg, "load" | "list" | "profile" | "suggest-aliases" | "report")
+}
+
+fn number(input: &str, flag: &str) -> Result<usize, String> {
+    input.parse().map_err(|_| format!("{} needs a whole number, got {:?}", flag, input))
+}
+
+fn decimal(input: &str, flag: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(n) if n.is_finite() && n >= 0.0 => Ok(n),
+        _ => Err(format!("{} needs a non-negative number, got {:?}", flag, input)),
+    }
+}
+
+fn fraction(input: &str, flag: &str) -> Result<f64, String> {
+    match decimal(input, flag) {
+        Ok(n) if n <= 1.0 => Ok(n),
+        _ => Err(format!("{} needs a number from 0 to 1, got {:?}", flag, input)),
+    }
+}
+
+fn value<'a>(args: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<String, String> {
+    args.next().cloned().ok_or_else(|| format!("{} needs a value", flag))
+}
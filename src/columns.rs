@@ -0,0 +1,256 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use serde_json::Value;
+
+// Canonical input columns. Each one can be matched by its own name or by any
+// alias listed for it in a column mapping file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Field {
+    MainIsland,
+    Region,
+    Province,
+    LegislativeDistrict,
+    Municipality,
+    DistrictEngineeringOffice,
+    ProjectId,
+    ProjectName,
+    TypeOfWork,
+    FundingYear,
+    ApprovedBudgetForContract,
+    ContractCost,
+    StartDate,
+    ActualCompletionDate,
+    Contractor,
+    ContractorCount,
+    PlannedCompletionDate,
+    ContractDurationDays,
+}
+
+const FIELDS: [Field; 18] = [
+    Field::MainIsland,
+    Field::Region,
+    Field::Province,
+    Field::LegislativeDistrict,
+    Field::Municipality,
+    Field::DistrictEngineeringOffice,
+    Field::ProjectId,
+    Field::ProjectName,
+    Field::TypeOfWork,
+    Field::FundingYear,
+    Field::ApprovedBudgetForContract,
+    Field::ContractCost,
+    Field::StartDate,
+    Field::ActualCompletionDate,
+    Field::Contractor,
+    Field::ContractorCount,
+    Field::PlannedCompletionDate,
+    Field::ContractDurationDays,
+];
+
+impl Field {
+    pub fn name(self) -> &'static str {
+        match self {
+            Field::MainIsland => "MainIsland",
+            Field::Region => "Region",
+            Field::Province => "Province",
+            Field::LegislativeDistrict => "LegislativeDistrict",
+            Field::Municipality => "Municipality",
+            Field::DistrictEngineeringOffice => "DistrictEngineeringOffice",
+            Field::ProjectId => "ProjectId",
+            Field::ProjectName => "ProjectName",
+            Field::TypeOfWork => "TypeOfWork",
+            Field::FundingYear => "FundingYear",
+            Field::ApprovedBudgetForContract => "ApprovedBudgetForContract",
+            Field::ContractCost => "ContractCost",
+            Field::StartDate => "StartDate",
+            Field::ActualCompletionDate => "ActualCompletionDate",
+            Field::Contractor => "Contractor",
+            Field::ContractorCount => "ContractorCount",
+            Field::PlannedCompletionDate => "PlannedCompletionDate",
+            Field::ContractDurationDays => "ContractDurationDays",
+        }
+    }
+
+    // Without these no row can become a Project
+    fn required(self) -> bool {
+        matches!(
+            self,
+            Field::MainIsland
+                | Field::Region
+                | Field::TypeOfWork
+                | Field::FundingYear
+                | Field::ApprovedBudgetForContract
+                | Field::ContractCost
+                | Field::StartDate
+                | Field::ActualCompletionDate
+                | Field::Contractor
+        )
+    }
+
+    fn from_name(name: &str) -> Option<Field> {
+        FIELDS.iter().copied().find(|f| f.name() == name)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MatchMode {
+    Exact,
+    // Ignores case and all whitespace, so "approved budget for contract"
+    // matches "ApprovedBudgetForContract"
+    Insensitive,
+}
+
+impl MatchMode {
+    fn key(self, header: &str) -> String {
+        match self {
+            MatchMode::Exact => header.to_string(),
+            MatchMode::Insensitive => header
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .flat_map(char::to_lowercase)
+                .collect(),
+        }
+    }
+}
+
+// Header aliases per canonical field, read from a JSON mapping file:
+//
+//   {
+//     "match": "insensitive",
+//     "columns": {
+//       "ApprovedBudgetForContract": ["ABC", "Approved Budget"],
+//       "ContractCost": "Contract Amount"
+//     }
+//   }
+#[derive(Clone)]
+pub struct ColumnMapping {
+    match_mode: MatchMode,
+    aliases: HashMap<Field, Vec<String>>,
+}
+
+impl Default for ColumnMapping {
+    fn default() -> Self {
+        ColumnMapping {
+            match_mode: MatchMode::Exact,
+            aliases: HashMap::new(),
+        }
+    }
+}
+
+impl ColumnMapping {
+    pub fn from_json_file(path: &str) -> Result<Self, Box<dyn Error>> {
+        let text = fs::read_to_string(path).map_err(|e| format!("Cannot read column mapping {}: {}", path, e))?;
+        let json: Value = serde_json::from_str(&text).map_err(|e| format!("Invalid column mapping {}: {}", path, e))?;
+
+        let mut mapping = ColumnMapping::default();
+        match json.get("match").and_then(Value::as_str) {
+            None | Some("exact") => {}
+            Some("insensitive") => mapping.match_mode = MatchMode::Insensitive,
+            Some(other) => return Err(format!("Unknown match mode {:?} in {} (use \"exact\" or \"insensitive\")", other, path).into()),
+        }
+
+        if let Some(columns) = json.get("columns") {
+            let columns = columns.as_object().ok_or_else(|| format!("\"columns\" in {} must be an object", path))?;
+            for (name, aliases) in columns {
+                let field = Field::from_name(name).ok_or_else(|| format!("Unknown column {:?} in {}", name, path))?;
+                let aliases: Vec<String> = match aliases {
+                    Value::String(alias) => vec![alias.clone()],
+                    Value::Array(items) => items
+                        .iter()
+                        .map(|v| v.as_str().map(str::to_string))
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| format!("Aliases for {} in {} must be strings", name, path))?,
+                    _ => return Err(format!("Aliases for {} in {} must be a string or a list", name, path).into()),
+                };
+                mapping.aliases.insert(field, aliases);
+            }
+        }
+        Ok(mapping)
+    }
+
+    // Header names accepted for a field, canonical name first
+    fn candidates(&self, field: Field) -> Vec<&str> {
+        let mut names = vec![field.name()];
+        if let Some(aliases) = self.aliases.get(&field) {
+            names.extend(aliases.iter().map(String::as_str));
+        }
+        names
+    }
+
+    // Locates every field in the header row. Fails before any rows are read
+    // if a required column cannot be found.
+    pub fn resolve(&self, headers: &csv::StringRecord) -> Result<Columns, Box<dyn Error>> {
+        let header_keys: Vec<String> = headers.iter().map(|h| self.match_mode.key(h)).collect();
+        let mut found: HashMap<Field, usize> = HashMap::new();
+        let mut missing: Vec<String> = Vec::new();
+
+        for field in FIELDS {
+            let candidates = self.candidates(field);
+            let position = candidates.iter().find_map(|name| {
+                let key = self.match_mode.key(name);
+                header_keys.iter().position(|h| *h == key)
+            });
+            match position {
+                Some(i) => {
+                    found.insert(field, i);
+                }
+                None if field.required() => missing.push(format!("{} (looked for: {})", field.name(), candidates.join(", "))),
+                None => {}
+            }
+        }
+
+        if !missing.is_empty() {
+            return Err(format!(
+                "Required column(s) not found:\n  {}\nHeaders in file: {}",
+                missing.join("\n  "),
+                headers.iter().collect::<Vec<_>>().join(", ")
+            )
+            .into());
+        }
+
+        let get = |field: Field| found.get(&field).copied();
+        Ok(Columns {
+            main_island: get(Field::MainIsland),
+            region: get(Field::Region),
+            province: get(Field::Province),
+            legislative_district: get(Field::LegislativeDistrict),
+            municipality: get(Field::Municipality),
+            district_engineering_office: get(Field::DistrictEngineeringOffice),
+            project_id: get(Field::ProjectId),
+            project_name: get(Field::ProjectName),
+            type_of_work: get(Field::TypeOfWork),
+            funding_year: get(Field::FundingYear),
+            approved_budget: get(Field::ApprovedBudgetForContract),
+            contract_cost: get(Field::ContractCost),
+            start_date: get(Field::StartDate),
+            actual_completion_date: get(Field::ActualCompletionDate),
+            contractor: get(Field::Contractor),
+            contractor_count: get(Field::ContractorCount),
+            planned_completion_date: get(Field::PlannedCompletionDate),
+            contract_duration_days: get(Field::ContractDurationDays),
+        })
+    }
+}
+
+// Column positions resolved once from the header row
+pub struct Columns {
+    pub main_island: Option<usize>,
+    pub region: Option<usize>,
+    pub province: Option<usize>,
+    pub legislative_district: Option<usize>,
+    pub municipality: Option<usize>,
+    pub district_engineering_office: Option<usize>,
+    pub project_id: Option<usize>,
+    pub project_name: Option<usize>,
+    pub type_of_work: Option<usize>,
+    pub funding_year: Option<usize>,
+    pub approved_budget: Option<usize>,
+    pub contract_cost: Option<usize>,
+    pub start_date: Option<usize>,
+    pub actual_completion_date: Option<usize>,
+    pub contractor: Option<usize>,
+    pub contractor_count: Option<usize>,
+    pub planned_completion_date: Option<usize>,
+    pub contract_duration_days: Option<usize>,
+}
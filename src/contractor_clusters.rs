@@ -0,0 +1,225 @@
+// Fuzzy grouping of contractor names that normalization leaves apart, such
+// as typos or a missing word. The output is a list of suggestions for a
+// reviewer; nothing is merged until the approved file is loaded as aliases.
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+use crate::project::Project;
+use crate::reports::truncate_name;
+
+pub const CSV_FILE: &str = "contractor_alias_suggestions.csv";
+
+// Two names in the same block are linked when either threshold is met
+#[derive(Clone, Debug)]
+pub struct ClusterOptions {
+    // Edit-distance similarity, 0.0 to 1.0
+    pub min_similarity: f64,
+    // Share of the shorter name's words found in the longer name, 0.0 to 1.0;
+    // only used when the shorter name has at least two words
+    pub min_token_overlap: f64,
+}
+
+impl Default for ClusterOptions {
+    fn default() -> Self {
+        ClusterOptions {
+            min_similarity: 0.85,
+            min_token_overlap: 1.0,
+        }
+    }
+}
+
+impl ClusterOptions {
+    // e.g. "similarity >= 0.85 or word overlap >= 1.00, blocked by first word"
+    pub fn label(&self) -> String {
+        format!(
+            "similarity >= {:.2} or word overlap >= {:.2}, blocked by first word",
+            self.min_similarity, self.min_token_overlap
+        )
+    }
+}
+
+// 1 - Levenshtein distance / length of the longer name, counted in chars
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    // Single-row dynamic programming over b
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    1.0 - row[b.len()] as f64 / longest as f64
+}
+
+// Share of the shorter name's distinct words that also appear in the other
+pub fn token_overlap(a: &str, b: &str) -> f64 {
+    let mut words_a: Vec<&str> = a.split_whitespace().collect();
+    let mut words_b: Vec<&str> = b.split_whitespace().collect();
+    words_a.sort_unstable();
+    words_a.dedup();
+    words_b.sort_unstable();
+    words_b.dedup();
+    let (shorter, longer) = if words_a.len() <= words_b.len() { (words_a, words_b) } else { (words_b, words_a) };
+    if shorter.is_empty() {
+        return 0.0;
+    }
+    let shared = shorter.iter().filter(|w| longer.contains(w)).count();
+    shared as f64 / shorter.len() as f64
+}
+
+// One proposed merge of alias into canonical. Both come from the same
+// cluster; the scores compare the two names directly, so a name pulled in
+// through a third name can score below the thresholds.
+#[derive(Clone, Debug)]
+pub struct AliasSuggestion {
+    pub cluster: usize,
+    pub alias: String,
+    pub canonical: String,
+    pub similarity: f64,
+    pub token_overlap: f64,
+    pub alias_projects: usize,
+    pub canonical_projects: usize,
+}
+
+// Clusters the contractor names of the projects. Only names sharing their
+// first word are compared. Each cluster's canonical name is its member with
+// the most projects, ties going to the alphabetically first.
+pub fn suggest_aliases(projects: &[Project], options: &ClusterOptions) -> Vec<AliasSuggestion> {
+    let mut project_counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for p in projects {
+        *project_counts.entry(p.contractor.as_str()).or_default() += 1;
+    }
+
+    // Block by first word
+    let mut blocks: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    for name in project_counts.keys() {
+        if let Some(first) = name.split_whitespace().next() {
+            blocks.entry(first).or_default().push(name);
+        }
+    }
+
+    let mut suggestions = Vec::new();
+    let mut cluster = 0;
+    for names in blocks.values() {
+        for members in link(names, options) {
+            let canonical = *members
+                .iter()
+                .max_by(|a, b| project_counts[**a].cmp(&project_counts[**b]).then(b.cmp(a)))
+                .expect("clusters have members");
+            cluster += 1;
+            for alias in members.iter().filter(|m| **m != canonical) {
+                suggestions.push(AliasSuggestion {
+                    cluster,
+                    alias: alias.to_string(),
+                    canonical: canonical.to_string(),
+                    similarity: similarity(alias, canonical),
+                    token_overlap: token_overlap(alias, canonical),
+                    alias_projects: project_counts[*alias],
+                    canonical_projects: project_counts[canonical],
+                });
+            }
+        }
+    }
+    suggestions
+}
+
+// Connected groups of two or more names, linking every pair that meets a
+// threshold
+fn link<'a>(names: &[&'a str], options: &ClusterOptions) -> Vec<Vec<&'a str>> {
+    // Union-find over indexes into names
+    let mut parent: Vec<usize> = (0..names.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    for i in 0..names.len() {
+        for j in i + 1..names.len() {
+            let (a, b) = (names[i], names[j]);
+            let shorter_words = a.split_whitespace().count().min(b.split_whitespace().count());
+            let linked = similarity(a, b) >= options.min_similarity
+                || (shorter_words >= 2 && token_overlap(a, b) >= options.min_token_overlap);
+            if linked {
+                let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                parent[ri] = rj;
+            }
+        }
+    }
+
+    let mut groups: HashMap<usize, Vec<&str>> = HashMap::new();
+    for (i, name) in names.iter().enumerate() {
+        groups.entry(root(&mut parent, i)).or_default().push(name);
+    }
+    let mut groups: Vec<Vec<&str>> = groups.into_values().filter(|g| g.len() > 1).collect();
+    groups.sort();
+    groups
+}
+
+pub fn print(suggestions: &[AliasSuggestion], options: &ClusterOptions) {
+    println!();
+    println!("Contractor Alias Suggestions");
+    println!("({})", options.label());
+    println!();
+    if suggestions.is_empty() {
+        println!("No similar contractor names found.");
+        return;
+    }
+    println!(
+        "| {:>7} | {:<45} | {:<45} | {:>10} | {:>7} | {:>8} |",
+        "Cluster", "Alias", "Canonical", "Similarity", "Overlap", "Projects"
+    );
+    println!("{}", "-".repeat(141));
+    for s in suggestions {
+        println!(
+            "| {:>7} | {:<45} | {:<45} | {:>10.2} | {:>7.2} | {:>8} |",
+            s.cluster,
+            truncate_name(&s.alias, 45),
+            truncate_name(&s.canonical, 45),
+            s.similarity,
+            s.token_overlap,
+            s.alias_projects
+        );
+    }
+}
+
+// The Alias and Canonical columns are what ContractorAliases reads back.
+// Approved is left blank; only rows marked yes are applied when loaded.
+pub fn write_csv(suggestions: &[AliasSuggestion], path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record([
+        "Alias",
+        "Canonical",
+        "Approved",
+        "Cluster",
+        "Similarity",
+        "TokenOverlap",
+        "AliasProjects",
+        "CanonicalProjects",
+    ])?;
+    for s in suggestions {
+        wtr.write_record(&[
+            s.alias.clone(),
+            s.canonical.clone(),
+            String::new(),
+            s.cluster.to_string(),
+            format!("{:.3}", s.similarity),
+            format!("{:.3}", s.token_overlap),
+            s.alias_projects.to_string(),
+            s.canonical_projects.to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,292 @@
+use std::collections::{BTreeMap, HashMap};
+use std::error::Error;
+use std::path::Path;
+use crate::project::Project;
+
+// Legal-form words and the spelling they are normalized to
+const SUFFIXES: [(&str, &str); 9] = [
+    ("CORP", "CORPORATION"),
+    ("CORPORATION", "CORPORATION"),
+    ("INC", "INC"),
+    ("INCORPORATED", "INC"),
+    ("CO", "COMPANY"),
+    ("COMPANY", "COMPANY"),
+    ("LTD", "LIMITED"),
+    ("ENT", "ENTERPRISES"),
+    ("ENTERPRISE", "ENTERPRISES"),
+];
+
+// Upper case, periods dropped, commas and repeated whitespace collapsed to
+// single spaces, and legal-form suffixes spelled one way:
+// "Alpha  Builders, Inc." -> "ALPHA BUILDERS INC"
+pub fn normalize(name: &str) -> String {
+    name.to_uppercase()
+        .replace('.', "")
+        .replace(',', " ")
+        .split_whitespace()
+        .map(|word| {
+            SUFFIXES
+                .iter()
+                .find(|(from, _)| *from == word)
+                .map_or(word, |(_, to)| *to)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+// Splits "NEW NAME (FORMERLY: OLD NAME)" into the current and former name.
+// Also accepts "FORMERLY KNOWN AS" and a missing colon.
+pub fn split_formerly(raw: &str) -> (String, Option<String>) {
+    // ASCII-only upper-casing keeps byte offsets valid for `raw`
+    let upper = raw.to_ascii_uppercase();
+    if let Some(open) = upper.find("(FORMERLY")
+        && let Some(close) = upper[open..].find(')')
+    {
+        let former = upper[open + "(FORMERLY".len()..open + close]
+            .trim_start_matches(" KNOWN AS")
+            .trim_start_matches(':')
+            .trim();
+        let current = format!("{} {}", &raw[..open], &raw[open + close + 1..]);
+        let former = (!former.is_empty()).then(|| former.to_string());
+        return (current.trim().to_string(), former);
+    }
+    (raw.trim().to_string(), None)
+}
+
+// Member firms of a joint venture written as "A / B JV". A name is only
+// split when it is marked as a JV or the row lists more than one contractor;
+// otherwise, or when fewer than two members are found, it is returned whole.
+pub fn jv_members(name: &str, contractor_count: Option<u32>) -> Vec<String> {
+    let upper = name.trim().to_ascii_uppercase();
+    let stripped = upper
+        .strip_suffix("JOINT VENTURE")
+        .or_else(|| upper.strip_suffix(" JV"))
+        .map(str::trim_end);
+    let is_jv = stripped.is_some() || contractor_count.is_some_and(|n| n > 1);
+    let members: Vec<String> = stripped
+        .unwrap_or(&upper)
+        .split('/')
+        .map(normalize)
+        .filter(|m| !m.is_empty())
+        .collect();
+    if is_jv && members.len() > 1 {
+        members
+    } else {
+        vec![name.to_string()]
+    }
+}
+
+// User-supplied Alias -> Canonical pairs, read from a CSV file with those two
+// columns. Both sides are compared after normalize(). A file that also has an
+// Approved column, like the alias suggestions export, only contributes the
+// rows marked yes.
+#[derive(Clone, Debug, Default)]
+pub struct ContractorAliases {
+    map: HashMap<String, String>,
+}
+
+impl ContractorAliases {
+    pub fn from_csv_file(path: &str) -> Result<Self, Box<dyn Error>> {
+        let mut rdr = csv::Reader::from_path(path).map_err(|e| format!("Cannot read contractor aliases {}: {}", path, e))?;
+        let headers = rdr.headers()?.clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|h| h.trim().eq_ignore_ascii_case(name))
+                .ok_or_else(|| format!("Contractor alias file {} needs Alias and Canonical columns", path))
+        };
+        let (alias_col, canonical_col) = (column("Alias")?, column("Canonical")?);
+        let approved_col = column("Approved").ok();
+
+        let mut aliases = ContractorAliases::default();
+        for record in rdr.records() {
+            let record = record?;
+            let alias = record.get(alias_col).unwrap_or("");
+            let canonical = record.get(canonical_col).unwrap_or("");
+            let approved = approved_col.is_none_or(|i| {
+                let answer = record.get(i).unwrap_or("").trim().to_ascii_lowercase();
+                matches!(answer.as_str(), "y" | "yes" | "true" | "1")
+            });
+            if approved && !alias.trim().is_empty() && !canonical.trim().is_empty() {
+                aliases.insert(alias, canonical);
+            }
+        }
+        Ok(aliases)
+    }
+
+    pub fn insert(&mut self, alias: &str, canonical: &str) {
+        self.map.insert(normalize(alias), normalize(canonical));
+    }
+
+    // Later files win over earlier ones for the same alias
+    pub fn extend(&mut self, other: &ContractorAliases) {
+        self.map.extend(other.map.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+}
+
+// Why a raw name maps to its canonical name
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MergeRule {
+    // Already in canonical form
+    Exact,
+    // Only case, spacing, punctuation or suffix spelling differed
+    Normalized,
+    // A "(FORMERLY: ...)" clause was dropped, or the name is a former name
+    // given in another row
+    Formerly,
+    // Listed in a contractor alias file
+    AliasFile,
+}
+
+impl MergeRule {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MergeRule::Exact => "exact",
+            MergeRule::Normalized => "normalized",
+            MergeRule::Formerly => "formerly",
+            MergeRule::AliasFile => "alias-file",
+        }
+    }
+}
+
+// Resolves raw contractor strings to canonical names. Former names learned
+// from "(FORMERLY: ...)" clauses apply to every project in the set.
+pub struct NameResolver<'a> {
+    aliases: &'a ContractorAliases,
+    // Normalized former name -> normalized current name
+    former: HashMap<String, String>,
+}
+
+impl<'a> NameResolver<'a> {
+    pub fn new<'p>(aliases: &'a ContractorAliases, raw_names: impl IntoIterator<Item = &'p str>) -> Self {
+        let mut former = HashMap::new();
+        for raw in raw_names {
+            if let (current, Some(old)) = split_formerly(raw) {
+                former.insert(normalize(&old), normalize(&current));
+            }
+        }
+        NameResolver { aliases, former }
+    }
+
+    pub fn resolve(&self, raw: &str) -> (String, MergeRule) {
+        let (current, old) = split_formerly(raw);
+        let mut name = normalize(&current);
+        let mut rule = if old.is_some() {
+            MergeRule::Formerly
+        } else if name == current {
+            MergeRule::Exact
+        } else {
+            MergeRule::Normalized
+        };
+
+        // Follow alias and former-name links; the bound stops alias cycles
+        for _ in 0..8 {
+            if let Some(canonical) = self.aliases.map.get(&name).filter(|c| **c != name) {
+                name = canonical.clone();
+                rule = MergeRule::AliasFile;
+            } else if let Some(current) = self.former.get(&name).filter(|c| **c != name) {
+                name = current.clone();
+                rule = rule.max(MergeRule::Formerly);
+            } else {
+                break;
+            }
+        }
+        (name, rule)
+    }
+}
+
+// One raw spelling that was folded into a canonical contractor name
+#[derive(Clone, Debug)]
+pub struct NameMerge {
+    pub canonical: String,
+    pub raw_name: String,
+    pub projects: usize,
+    pub rule: MergeRule,
+}
+
+// Raw spellings for every canonical name that more than one spelling
+// resolved to, sorted by canonical then raw name
+pub fn merges(projects: &[Project], resolver: &NameResolver) -> Vec<NameMerge> {
+    let mut by_canonical: BTreeMap<String, BTreeMap<&str, usize>> = BTreeMap::new();
+    for p in projects {
+        *by_canonical
+            .entry(p.contractor.clone())
+            .or_default()
+            .entry(p.contractor_raw.as_str())
+            .or_default() += 1;
+    }
+    by_canonical
+        .into_iter()
+        .filter(|(_, raw_names)| raw_names.len() > 1)
+        .flat_map(|(canonical, raw_names)| {
+            raw_names.into_iter().map(move |(raw_name, projects)| NameMerge {
+                canonical: canonical.clone(),
+                raw_name: raw_name.to_string(),
+                projects,
+                rule: resolver.resolve(raw_name).1,
+            })
+        })
+        .collect()
+}
+
+pub fn write_merges_csv(merges: &[NameMerge], path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record(["Canonical", "RawName", "Projects", "Rule"])?;
+    for m in merges {
+        wtr.write_record(&[
+            m.canonical.clone(),
+            m.raw_name.clone(),
+            m.projects.to_string(),
+            m.rule.label().to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_case_punctuation_and_suffixes() {
+        assert_eq!(normalize("Alpha  Builders, Inc."), "ALPHA BUILDERS INC");
+        assert_eq!(normalize("Legacy Construction Corp."), "LEGACY CONSTRUCTION CORPORATION");
+        assert_eq!(normalize("St. Timothy Co"), "ST TIMOTHY COMPANY");
+    }
+
+    #[test]
+    fn splits_formerly_clauses() {
+        let expected = ("NEW ALPHA CORP".to_string(), Some("ALPHA CORP".to_string()));
+        assert_eq!(split_formerly("NEW ALPHA CORP (FORMERLY: ALPHA CORP)"), expected);
+        assert_eq!(split_formerly("NEW ALPHA CORP (Formerly Alpha Corp)").0, "NEW ALPHA CORP");
+        assert_eq!(split_formerly("NEW ALPHA CORP (FORMERLY Alpha Corp)").1.as_deref(), Some("ALPHA CORP"));
+        assert_eq!(split_formerly("NEW ALPHA CORP (FORMERLY KNOWN AS ALPHA CORP)"), expected);
+        assert_eq!(split_formerly("Dasmariñas Builders"), ("Dasmariñas Builders".to_string(), None));
+    }
+
+    #[test]
+    fn resolves_former_names_and_aliases() {
+        let mut aliases = ContractorAliases::default();
+        aliases.insert("Beta Enginering", "Beta Engineering");
+        let resolver = NameResolver::new(&aliases, ["NEW ALPHA CORP (FORMERLY: ALPHA CORP)"]);
+        assert_eq!(resolver.resolve("Alpha Corp."), ("NEW ALPHA CORPORATION".to_string(), MergeRule::Formerly));
+        assert_eq!(resolver.resolve("BETA ENGINERING"), ("BETA ENGINEERING".to_string(), MergeRule::AliasFile));
+        assert_eq!(resolver.resolve("GAMMA DEV"), ("GAMMA DEV".to_string(), MergeRule::Exact));
+    }
+
+    #[test]
+    fn splits_joint_ventures_into_members() {
+        assert_eq!(jv_members("Gamma Dev / Delta Corp JV", None), ["GAMMA DEV", "DELTA CORPORATION"]);
+        assert_eq!(jv_members("GAMMA DEV/DELTA CORP JOINT VENTURE", None), ["GAMMA DEV", "DELTA CORPORATION"]);
+        // Not marked as a JV, but the row lists two contractors
+        assert_eq!(jv_members("GAMMA DEV / DELTA CORP", Some(2)), ["GAMMA DEV", "DELTA CORPORATION"]);
+    }
+
+    #[test]
+    fn keeps_single_firms_whole() {
+        assert_eq!(jv_members("GAMMA DEV / DELTA CORP", Some(1)), ["GAMMA DEV / DELTA CORP"]);
+        assert_eq!(jv_members("GAMMA DEV JV", None), ["GAMMA DEV JV"]);
+        assert_eq!(jv_members("SUNWEST INC", Some(2)), ["SUNWEST INC"]);
+    }
+}
@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs;
+use std::path::Path;
+use crate::contractor_names::{self, ContractorAliases, NameMerge, NameResolver};
+use crate::loader::{read_projects, LoadOptions, LoadReport, RejectedRow};
+use crate::project::{FundingYears, Project};
+
+// What to do when an incoming row has a ProjectId that is already loaded.
+// Rows without a ProjectId are never treated as duplicates.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DuplicatePolicy {
+    // Keep the project already in the dataset and drop the incoming row
+    KeepFirst,
+    // Replace the loaded project with the incoming row
+    KeepLast,
+    // Keep the loaded project but fill its blank optional fields from the incoming row
+    Merge,
+}
+
+impl DuplicatePolicy {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "f" | "first" | "keep-first" => Some(DuplicatePolicy::KeepFirst),
+            "l" | "last" | "keep-last" => Some(DuplicatePolicy::KeepLast),
+            "m" | "merge" => Some(DuplicatePolicy::Merge),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DuplicatePolicy::KeepFirst => "keep-first",
+            DuplicatePolicy::KeepLast => "keep-last",
+            DuplicatePolicy::Merge => "merge",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct DuplicateStats {
+    pub dropped: usize,
+    pub replaced: usize,
+    pub merged: usize,
+}
+
+impl DuplicateStats {
+    pub fn total(&self) -> usize {
+        self.dropped + self.replaced + self.merged
+    }
+}
+
+pub struct LoadedSource {
+    pub id: usize,
+    pub path: String,
+    pub funding_years: FundingYears,
+    pub policy: DuplicatePolicy,
+    pub added: usize,
+    pub duplicates: DuplicateStats,
+    // Rows of this file that could not become projects
+    pub rejected_rows: Vec<RejectedRow>,
+    // Every valid row as read, before de-duplication, so the dataset can be
+    // rebuilt without this file's influence when an earlier file is unloaded
+    rows: Vec<Project>,
+}
+
+// Projects from every loaded file. Each project remembers the file it came
+// from so that file can be unloaded again. Contractor names are kept
+// canonical across all loaded files.
+#[derive(Default)]
+pub struct Dataset {
+    projects: Vec<Project>,
+    sources: Vec<LoadedSource>,
+    next_source_id: usize,
+    contractor_aliases: ContractorAliases,
+}
+
+impl Dataset {
+    pub fn projects(&self) -> &[Project] {
+        &self.projects
+    }
+
+    pub fn sources(&self) -> &[LoadedSource] {
+        &self.sources
+    }
+
+    // Projects currently in the dataset that came from a source
+    pub fn project_count(&self, source_id: usize) -> usize {
+        self.projects.iter().filter(|p| p.source_id == source_id).count()
+    }
+
+    // Union of the funding-year windows of all loaded files
+    pub fn funding_years(&self) -> FundingYears {
+        self.sources
+            .iter()
+            .map(|s| s.funding_years.clone())
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or_default()
+    }
+
+    // Raw contractor spellings that were merged into one canonical name
+    pub fn contractor_merges(&self) -> Vec<NameMerge> {
+        let resolver = self.name_resolver();
+        contractor_names::merges(&self.projects, &resolver)
+    }
+
+    // Reads a CSV file and adds its projects, returning the load summary
+    pub fn load_file(&mut self, path: &str, options: &LoadOptions) -> Result<LoadReport, Box<dyn Error>> {
+        let (projects, mut report) = read_projects(path, options)?;
+        if let Some(earlier) = self.sources.iter().find(|s| same_file(&s.path, path)) {
+            report.warnings.push(format!(
+                "{} is already loaded as file #{}; rows without a ProjectId will be counted twice",
+                path, earlier.id
+            ));
+        }
+        self.contractor_aliases.extend(&options.contractor_aliases);
+        let source = self.add_source(path, options.funding_years.clone(), projects, options.duplicate_policy);
+        let source_id = source.id;
+        report.source_id = source_id;
+        report.added = source.added;
+        report.duplicate_policy = Some(source.policy);
+        report.duplicates = source.duplicates;
+        report.dataset_projects = self.projects.len();
+        if let Some(source) = self.sources.iter_mut().find(|s| s.id == source_id) {
+            source.rejected_rows = report.rejected_rows.clone();
+        }
+        Ok(report)
+    }
+
+    // Adds one file's projects, de-duplicating by ProjectId against everything
+    // already loaded (including earlier rows of the same file).
+    pub fn add_source(
+        &mut self,
+        path: &str,
+        funding_years: FundingYears,
+        mut projects: Vec<Project>,
+        policy: DuplicatePolicy,
+    ) -> &LoadedSource {
+        // Source ids start at 1; 0 marks a project not yet added to a dataset
+        self.next_source_id += 1;
+        let source_id = self.next_source_id;
+
+        for project in &mut projects {
+            project.source_id = source_id;
+        }
+        let (added, duplicates) = self.apply(&projects, policy);
+
+        self.sources.push(LoadedSource {
+            id: source_id,
+            path: path.to_string(),
+            funding_years,
+            policy,
+            added,
+            duplicates,
+            rejected_rows: Vec::new(),
+            rows: projects,
+        });
+        self.canonicalize_contractors();
+        self.sources.last().unwrap()
+    }
+
+    // Removes a file. The remaining files are replayed in load order with
+    // their own policies, so rows the removed file dropped, replaced or
+    // merged into come back exactly as if it had never been loaded.
+    pub fn unload(&mut self, source_id: usize) -> Option<LoadedSource> {
+        let position = self.sources.iter().position(|s| s.id == source_id)?;
+        let removed = self.sources.remove(position);
+
+        self.projects.clear();
+        let mut sources = std::mem::take(&mut self.sources);
+        for source in &mut sources {
+            (source.added, source.duplicates) = self.apply(&source.rows, source.policy);
+        }
+        self.sources = sources;
+        // Former names learned from the removed rows no longer apply
+        self.canonicalize_contractors();
+        Some(removed)
+    }
+
+    // De-duplicates one file's rows by ProjectId against everything already
+    // in the dataset (including earlier rows of the same file)
+    fn apply(&mut self, projects: &[Project], policy: DuplicatePolicy) -> (usize, DuplicateStats) {
+        let mut by_id: HashMap<String, usize> = self
+            .projects
+            .iter()
+            .enumerate()
+            .filter_map(|(i, p)| p.project_id.clone().map(|id| (id, i)))
+            .collect();
+
+        let mut added = 0;
+        let mut duplicates = DuplicateStats::default();
+        for project in projects.iter().cloned() {
+            let existing = project.project_id.as_ref().and_then(|id| by_id.get(id).copied());
+            match (existing, policy) {
+                (None, _) => {
+                    if let Some(id) = &project.project_id {
+                        by_id.insert(id.clone(), self.projects.len());
+                    }
+                    self.projects.push(project);
+                    added += 1;
+                }
+                (Some(_), DuplicatePolicy::KeepFirst) => duplicates.dropped += 1,
+                (Some(i), DuplicatePolicy::KeepLast) => {
+                    self.projects[i] = project;
+                    duplicates.replaced += 1;
+                }
+                (Some(i), DuplicatePolicy::Merge) => {
+                    merge_missing(&mut self.projects[i], project);
+                    duplicates.merged += 1;
+                }
+            }
+        }
+        (added, duplicates)
+    }
+
+    pub fn rejected_row_count(&self) -> usize {
+        self.sources.iter().map(|s| s.rejected_rows.len()).sum()
+    }
+
+    // Rejected rows of every loaded file, tagged with the file they came from
+    pub fn write_rejected_rows(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(["File", "SourceId", "Row", "Column", "RawValue", "Reason"])?;
+        for source in &self.sources {
+            for r in &source.rejected_rows {
+                wtr.write_record(&[
+                    source.path.clone(),
+                    source.id.to_string(),
+                    r.row.to_string(),
+                    r.column.clone(),
+                    r.raw_value.clone(),
+                    r.reason.code().to_string(),
+                ])?;
+            }
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    fn name_resolver(&self) -> NameResolver<'_> {
+        NameResolver::new(&self.contractor_aliases, self.projects.iter().map(|p| p.contractor_raw.as_str()))
+    }
+
+    // Re-resolves every project's contractor and JV members from its raw
+    // name, since a new file can add aliases or former names that affect
+    // earlier projects
+    fn canonicalize_contractors(&mut self) {
+        let canonical: Vec<(String, Vec<String>)> = {
+            let resolver = self.name_resolver();
+            self.projects
+                .iter()
+                .map(|p| {
+                    let name = resolver.resolve(&p.contractor_raw).0;
+                    let mut members = contractor_names::jv_members(&name, p.contractor_count);
+                    if members.len() > 1 {
+                        members = members.iter().map(|m| resolver.resolve(m).0).collect();
+                    } else {
+                        members.clear();
+                    }
+                    (name, members)
+                })
+                .collect()
+        };
+        for (project, (name, members)) in self.projects.iter_mut().zip(canonical) {
+            project.contractor = name;
+            project.contractor_members = members;
+        }
+    }
+}
+
+// Compares resolved paths when both exist, so "./a.csv" matches "a.csv"
+fn same_file(a: &str, b: &str) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+fn merge_missing(existing: &mut Project, incoming: Project) {
+    fn fill<T>(slot: &mut Option<T>, value: Option<T>) {
+        if slot.is_none() {
+            *slot = value;
+        }
+    }
+    fill(&mut existing.province, incoming.province);
+    fill(&mut existing.legislative_district, incoming.legislative_district);
+    fill(&mut existing.municipality, incoming.municipality);
+    fill(&mut existing.district_engineering_office, incoming.district_engineering_office);
+    fill(&mut existing.project_name, incoming.project_name);
+    fill(&mut existing.contractor_count, incoming.contractor_count);
+    fill(&mut existing.planned_completion_date, incoming.planned_completion_date);
+    fill(&mut existing.contract_duration_days, incoming.contract_duration_days);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::test_project;
+
+    // Rows with the given ProjectIds; the name tells which file they came from
+    fn rows(file: &str, ids: &[&str]) -> Vec<Project> {
+        ids.iter()
+            .map(|id| Project {
+                project_id: Some(id.to_string()),
+                project_name: Some(format!("{} {}", file, id)),
+                ..test_project()
+            })
+            .collect()
+    }
+
+    fn names(dataset: &Dataset) -> Vec<String> {
+        let mut names: Vec<String> = dataset.projects().iter().filter_map(|p| p.project_name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    fn load(dataset: &mut Dataset, file: &str, ids: &[&str], policy: DuplicatePolicy) -> usize {
+        dataset.add_source(file, FundingYears::default(), rows(file, ids), policy).id
+    }
+
+    #[test]
+    fn keep_first_restores_dropped_rows_on_unload() {
+        let mut dataset = Dataset::default();
+        let a = load(&mut dataset, "a", &["P1", "P2"], DuplicatePolicy::KeepFirst);
+        let b = load(&mut dataset, "b", &["P1", "P9"], DuplicatePolicy::KeepFirst);
+        assert_eq!(names(&dataset), ["a P1", "a P2", "b P9"]);
+        assert_eq!(dataset.sources()[1].duplicates.dropped, 1);
+
+        dataset.unload(a);
+        assert_eq!(names(&dataset), ["b P1", "b P9"]);
+        assert_eq!(dataset.project_count(b), 2);
+    }
+
+    #[test]
+    fn keep_last_restores_replaced_rows_on_unload() {
+        let mut dataset = Dataset::default();
+        let a = load(&mut dataset, "a", &["P1", "P2"], DuplicatePolicy::KeepFirst);
+        let b = load(&mut dataset, "b", &["P1"], DuplicatePolicy::KeepLast);
+        assert_eq!(names(&dataset), ["a P2", "b P1"]);
+
+        dataset.unload(b);
+        assert_eq!(names(&dataset), ["a P1", "a P2"]);
+        assert_eq!(dataset.project_count(a), 2);
+    }
+
+    #[test]
+    fn merge_drops_merged_values_on_unload() {
+        let mut dataset = Dataset::default();
+        let mut first = rows("a", &["P1"]);
+        first[0].province = None;
+        dataset.add_source("a", FundingYears::default(), first, DuplicatePolicy::KeepFirst);
+        let mut second = rows("b", &["P1"]);
+        second[0].province = Some("Cavite".to_string());
+        let b = dataset.add_source("b", FundingYears::default(), second, DuplicatePolicy::Merge).id;
+        assert_eq!(dataset.projects()[0].province.as_deref(), Some("Cavite"));
+        assert_eq!(names(&dataset), ["a P1"]);
+
+        dataset.unload(b);
+        assert_eq!(dataset.projects().len(), 1);
+        assert_eq!(dataset.projects()[0].province, None);
+    }
+
+    #[test]
+    fn rows_without_project_id_are_never_duplicates() {
+        let mut dataset = Dataset::default();
+        let row = || Project { project_id: None, ..test_project() };
+        dataset.add_source("a", FundingYears::default(), vec![row(), row()], DuplicatePolicy::KeepFirst);
+        assert_eq!(dataset.projects().len(), 2);
+    }
+}
@@ -0,0 +1,149 @@
+use std::collections::HashMap;
+use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+// Tried in order, so put the most common export format first.
+// Timestamps are accepted and truncated to their date.
+pub const DEFAULT_DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%m/%d/%Y",
+    "%m/%d/%Y %H:%M",
+    "%m/%d/%Y %H:%M:%S",
+    "%d-%b-%y",
+    "%d-%b-%Y",
+];
+
+pub const EXCEL_SERIAL: &str = "excel-serial";
+
+// Excel's day 0 (the 1900 leap-year bug makes 1899-12-30 the usable epoch)
+const EXCEL_EPOCH: (i32, u32, u32) = (1899, 12, 30);
+// 9999-12-31 is the largest serial Excel can display
+const EXCEL_MAX_SERIAL: f64 = 2_958_465.0;
+
+pub struct DateParser {
+    formats: Vec<String>,
+    excel_serial: bool,
+    // Formats matched by the row being parsed, counted once it is kept
+    row_matches: Vec<String>,
+    matches: HashMap<String, usize>,
+}
+
+impl DateParser {
+    pub fn new(formats: Vec<String>, excel_serial: bool) -> Self {
+        DateParser {
+            formats,
+            excel_serial,
+            row_matches: Vec::new(),
+            matches: HashMap::new(),
+        }
+    }
+
+    pub fn parse(&mut self, raw: &str) -> Option<NaiveDate> {
+        let value = raw.trim();
+        if value.is_empty() {
+            return None;
+        }
+
+        for format in &self.formats {
+            let parsed = NaiveDate::parse_from_str(value, format)
+                .ok()
+                .or_else(|| NaiveDateTime::parse_from_str(value, format).ok().map(|dt| dt.date()));
+            if let Some(date) = parsed {
+                self.row_matches.push(format.clone());
+                return Some(date);
+            }
+        }
+
+        if self.excel_serial
+            && let Some(date) = parse_excel_serial(value)
+        {
+            self.row_matches.push(EXCEL_SERIAL.to_string());
+            return Some(date);
+        }
+        None
+    }
+
+    // Ends the current row. Its formats are counted only if the row was kept,
+    // once each even when both date columns used the same format.
+    pub fn finish_row(&mut self, kept: bool) {
+        let mut formats = std::mem::take(&mut self.row_matches);
+        if kept {
+            formats.sort();
+            formats.dedup();
+            for format in formats {
+                *self.matches.entry(format).or_default() += 1;
+            }
+        }
+    }
+
+    // Number of kept rows each format matched, in the order the formats were
+    // tried. A row whose two dates used different formats counts under both.
+    pub fn match_counts(&self) -> Vec<(String, usize)> {
+        self.formats
+            .iter()
+            .map(String::as_str)
+            .chain(self.excel_serial.then_some(EXCEL_SERIAL))
+            .filter_map(|f| self.matches.get(f).map(|count| (f.to_string(), *count)))
+            .collect()
+    }
+}
+
+fn parse_excel_serial(value: &str) -> Option<NaiveDate> {
+    let serial = value.parse::<f64>().ok()?;
+    if !(1.0..=EXCEL_MAX_SERIAL).contains(&serial) {
+        return None;
+    }
+    let (y, m, d) = EXCEL_EPOCH;
+    NaiveDate::from_ymd_opt(y, m, d)?.checked_add_signed(Duration::days(serial.trunc() as i64))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parser(excel_serial: bool) -> DateParser {
+        DateParser::new(DEFAULT_DATE_FORMATS.iter().map(|f| f.to_string()).collect(), excel_serial)
+    }
+
+    fn ymd(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn parses_default_formats() {
+        let mut p = parser(false);
+        assert_eq!(p.parse("2022-03-15"), ymd(2022, 3, 15));
+        assert_eq!(p.parse("03/15/2022"), ymd(2022, 3, 15));
+        assert_eq!(p.parse("15-Mar-22"), ymd(2022, 3, 15));
+        assert_eq!(p.parse("15-Mar-2022"), ymd(2022, 3, 15));
+    }
+
+    #[test]
+    fn truncates_timestamps_to_dates() {
+        let mut p = parser(false);
+        assert_eq!(p.parse("2022-03-15 13:45:00"), ymd(2022, 3, 15));
+        assert_eq!(p.parse("2022-03-15T13:45:00"), ymd(2022, 3, 15));
+        assert_eq!(p.parse("03/15/2022 13:45"), ymd(2022, 3, 15));
+    }
+
+    #[test]
+    fn excel_serials_only_when_enabled() {
+        assert_eq!(parser(false).parse("44600"), None);
+        let mut p = parser(true);
+        assert_eq!(p.parse("44600"), ymd(2022, 2, 8));
+        assert_eq!(p.parse("44600.75"), ymd(2022, 2, 8));
+        assert_eq!(p.parse("0"), None);
+    }
+
+    #[test]
+    fn counts_each_kept_row_once_per_format() {
+        let mut p = parser(true);
+        p.parse("2022-03-15");
+        p.parse("2022-04-15");
+        p.finish_row(true);
+        p.parse("44600");
+        p.finish_row(false);
+        assert_eq!(p.match_counts(), vec![("%Y-%m-%d".to_string(), 1)]);
+    }
+}
@@ -0,0 +1,31 @@
+//! Analysis of DPWH flood-control project extracts.
+//!
+//! Load one or more CSV files into a [`Dataset`], then compute any of the
+//! reports as typed rows:
+//!
+//! ```no_run
+//! use hello_world::{load, reports, LoadOptions};
+//!
+//! let dataset = load("projects.csv", &LoadOptions::default())?;
+//! let options = reports::RegionalOptions::default();
+//! for row in reports::regional_summary(&dataset, &options) {
+//!     println!("{}: {:.2}", row.group.join(" / "), row.efficiency_score);
+//! }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub mod columns;
+pub mod contractor_clusters;
+pub mod contractor_names;
+pub mod dataset;
+pub mod dates;
+pub mod loader;
+pub mod metrics;
+pub mod money;
+pub mod profile;
+pub mod project;
+pub mod reports;
+
+pub use dataset::{Dataset, DuplicatePolicy};
+pub use loader::{load, LoadOptions, LoadReport};
+pub use project::{FundingYears, Project};
@@ -0,0 +1,307 @@
+use std::collections::HashMap;
+use std::error::Error;
+use crate::columns::{ColumnMapping, Columns};
+use crate::contractor_names::ContractorAliases;
+use crate::dataset::{Dataset, DuplicatePolicy, DuplicateStats};
+use crate::dates::{DateParser, DEFAULT_DATE_FORMATS};
+use crate::money::MoneyParser;
+use crate::project::{FundingYears, Project};
+
+// Settings that control how a CSV file is read into projects
+#[derive(Clone)]
+pub struct LoadOptions {
+    pub funding_years: FundingYears,
+    pub date_formats: Vec<String>,
+    pub excel_serial_dates: bool,
+    pub column_mapping: ColumnMapping,
+    pub duplicate_policy: DuplicatePolicy,
+    // Added to the dataset's aliases; they apply to every loaded file
+    pub contractor_aliases: ContractorAliases,
+}
+
+impl Default for LoadOptions {
+    fn default() -> Self {
+        LoadOptions {
+            funding_years: FundingYears::default(),
+            date_formats: DEFAULT_DATE_FORMATS.iter().map(|f| f.to_string()).collect(),
+            excel_serial_dates: false,
+            column_mapping: ColumnMapping::default(),
+            duplicate_policy: DuplicatePolicy::KeepFirst,
+            contractor_aliases: ContractorAliases::default(),
+        }
+    }
+}
+
+// Everything learned while reading one file, for the load summary
+#[derive(Clone, Debug, Default)]
+pub struct LoadReport {
+    pub path: String,
+    pub funding_years: FundingYears,
+    pub rows_read: usize,
+    // Valid rows inside the funding-year window
+    pub rows_kept: usize,
+    pub rejected_rows: Vec<RejectedRow>,
+    pub money_normalized: usize,
+    pub date_matches: Vec<(String, usize)>,
+    // Conditions that load but can distort totals, e.g. double counting
+    pub warnings: Vec<String>,
+    // Filled in once the projects are added to a dataset
+    pub source_id: usize,
+    pub added: usize,
+    pub duplicate_policy: Option<DuplicatePolicy>,
+    pub duplicates: DuplicateStats,
+    pub dataset_projects: usize,
+}
+
+impl LoadReport {
+    pub fn print(&self) {
+        println!(
+            "Processing dataset... ({} rows loaded, {} filtered for {})",
+            self.rows_read,
+            self.rows_kept,
+            self.funding_years.label()
+        );
+        if let Some(policy) = self.duplicate_policy {
+            let dup = self.duplicates;
+            if dup.total() > 0 {
+                println!(
+                    "Duplicate ProjectIds ({}): {} dropped, {} replaced, {} merged",
+                    policy.label(),
+                    dup.dropped,
+                    dup.replaced,
+                    dup.merged
+                );
+            }
+            println!(
+                "{} projects added as file #{}; dataset now holds {} projects",
+                self.added, self.source_id, self.dataset_projects
+            );
+        }
+
+        for warning in &self.warnings {
+            println!("Warning: {}", warning);
+        }
+
+        if self.money_normalized > 0 {
+            println!(
+                "{} money values normalized (currency markers, separators or accounting negatives)",
+                self.money_normalized
+            );
+        }
+
+        if !self.date_matches.is_empty() {
+            println!("Date formats matched (loaded rows):");
+            for (format, count) in &self.date_matches {
+                println!("  {:<20} {:>8}", format, count);
+            }
+        }
+
+        if !self.rejected_rows.is_empty() {
+            println!("{} rows rejected:", self.rejected_rows.len());
+            for (reason, count) in self.rejections_by_reason() {
+                println!("  {:<16} {:>8}", reason.code(), count);
+            }
+        }
+    }
+
+    pub fn rejections_by_reason(&self) -> Vec<(RejectReason, usize)> {
+        let mut by_reason: HashMap<RejectReason, usize> = HashMap::new();
+        for r in &self.rejected_rows {
+            *by_reason.entry(r.reason).or_default() += 1;
+        }
+        let mut by_reason: Vec<_> = by_reason.into_iter().collect();
+        by_reason.sort();
+        by_reason
+    }
+}
+
+// Loads a single file into a new dataset
+pub fn load(path: &str, options: &LoadOptions) -> Result<Dataset, Box<dyn Error>> {
+    let mut dataset = Dataset::default();
+    dataset.load_file(path, options)?;
+    Ok(dataset)
+}
+
+// Parses a CSV file into projects without adding them to a dataset
+pub fn read_projects(path: &str, options: &LoadOptions) -> Result<(Vec<Project>, LoadReport), Box<dyn Error>> {
+    let funding_years = options.funding_years.clone();
+    let mut date_parser = DateParser::new(options.date_formats.clone(), options.excel_serial_dates);
+    let mut money_parser = MoneyParser::default();
+
+    let mut rdr = csv::Reader::from_path(path).map_err(|e| format!("Cannot read {}: {}", path, e))?;
+    let headers = rdr.headers()?.clone();
+    let columns = options.column_mapping.resolve(&headers)?;
+    let mut total_rows = 0;
+    let mut projects: Vec<Project> = Vec::new();
+    let mut rejected_rows: Vec<RejectedRow> = Vec::new();
+
+    for result in rdr.records() {
+        total_rows += 1;
+        let record = match result {
+            Ok(r) => r,
+            Err(e) => {
+                rejected_rows.push(RejectedRow {
+                    row: total_rows,
+                    column: String::new(),
+                    raw_value: e.to_string(),
+                    reason: RejectReason::CsvParse,
+                });
+                continue;
+            }
+        };
+
+        let parsed = parse_record(&record, &columns, &funding_years, &mut date_parser, &mut money_parser, total_rows);
+        date_parser.finish_row(matches!(parsed, Ok(Some(_))));
+        match parsed {
+            Ok(Some(project)) => projects.push(project),
+            // Outside the funding-year window
+            Ok(None) => {}
+            Err(rejected) => rejected_rows.push(rejected),
+        }
+    }
+
+    let mut warnings = Vec::new();
+    if columns.project_id.is_none() {
+        warnings.push("no ProjectId column; rows cannot be de-duplicated against other files".to_string());
+    }
+
+    let report = LoadReport {
+        path: path.to_string(),
+        funding_years,
+        rows_read: total_rows,
+        rows_kept: projects.len(),
+        rejected_rows,
+        money_normalized: money_parser.normalized_count(),
+        date_matches: date_parser.match_counts(),
+        warnings,
+        ..LoadReport::default()
+    };
+    Ok((projects, report))
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum RejectReason {
+    CsvParse,
+    InvalidYear,
+    MissingValue,
+    InvalidNumber,
+    InvalidDate,
+}
+
+impl RejectReason {
+    pub fn code(&self) -> &'static str {
+        match self {
+            RejectReason::CsvParse => "CSV_PARSE",
+            RejectReason::InvalidYear => "INVALID_YEAR",
+            RejectReason::MissingValue => "MISSING_VALUE",
+            RejectReason::InvalidNumber => "INVALID_NUMBER",
+            RejectReason::InvalidDate => "INVALID_DATE",
+        }
+    }
+}
+
+// One input row that could not become a Project
+#[derive(Clone, Debug)]
+pub struct RejectedRow {
+    // 1-based data row, not counting the header
+    pub row: usize,
+    pub column: String,
+    pub raw_value: String,
+    pub reason: RejectReason,
+}
+
+// Returns Ok(None) for valid rows outside the funding-year window.
+fn parse_record(
+    record: &csv::StringRecord,
+    columns: &Columns,
+    funding_years: &FundingYears,
+    date_parser: &mut DateParser,
+    money_parser: &mut MoneyParser,
+    row: usize,
+) -> Result<Option<Project>, RejectedRow> {
+    let reject = |column: &str, raw: &str, reason: RejectReason| RejectedRow {
+        row,
+        column: column.to_string(),
+        raw_value: raw.to_string(),
+        reason,
+    };
+    let raw = |idx: Option<usize>| idx.and_then(|i| record.get(i)).unwrap_or("");
+    let text = |column: &str, idx: Option<usize>| {
+        let v = raw(idx);
+        if v.trim().is_empty() {
+            Err(reject(column, v, RejectReason::MissingValue))
+        } else {
+            Ok(v.to_string())
+        }
+    };
+    let mut money = |column: &str, idx: Option<usize>| {
+        let v = raw(idx);
+        money_parser.parse(v).ok_or_else(|| reject(column, v, RejectReason::InvalidNumber))
+    };
+    let mut date = |column: &str, idx: Option<usize>| {
+        let v = raw(idx);
+        date_parser.parse(v).ok_or_else(|| reject(column, v, RejectReason::InvalidDate))
+    };
+
+    // FundingYear validation and filter
+    let fy = raw(columns.funding_year);
+    let funding_year = fy.parse::<i32>().map_err(|_| reject("FundingYear", fy, RejectReason::InvalidYear))?;
+    if !funding_years.contains(funding_year) {
+        return Ok(None);
+    }
+
+    let region = text("Region", columns.region)?;
+    let main_island = text("MainIsland", columns.main_island)?;
+    let contractor = text("Contractor", columns.contractor)?;
+    let type_of_work = text("TypeOfWork", columns.type_of_work)?;
+    let approved_budget = money("ApprovedBudgetForContract", columns.approved_budget)?;
+    let contract_cost = money("ContractCost", columns.contract_cost)?;
+    let start_date = date("StartDate", columns.start_date)?;
+    let actual_completion_date = date("ActualCompletionDate", columns.actual_completion_date)?;
+
+    // Optional, but a value that is present must parse; otherwise the row
+    // would silently fall back to measuring duration
+    let planned_completion_date = match optional_text(record, columns.planned_completion_date) {
+        Some(_) => Some(date("PlannedCompletionDate", columns.planned_completion_date)?),
+        None => None,
+    };
+    let contract_duration_days = match optional_text(record, columns.contract_duration_days) {
+        Some(v) => match v.parse::<f64>() {
+            Ok(days) if days.is_finite() && days >= 0.0 => Some(days.round() as i64),
+            _ => return Err(reject("ContractDurationDays", &v, RejectReason::InvalidNumber)),
+        },
+        None => None,
+    };
+
+    // Optional columns: blank or missing values are kept as None
+    Ok(Some(Project {
+        main_island,
+        region,
+        province: optional_text(record, columns.province),
+        legislative_district: optional_text(record, columns.legislative_district),
+        municipality: optional_text(record, columns.municipality),
+        district_engineering_office: optional_text(record, columns.district_engineering_office),
+        project_id: optional_text(record, columns.project_id),
+        project_name: optional_text(record, columns.project_name),
+        type_of_work,
+        approved_budget,
+        contract_cost,
+        start_date,
+        actual_completion_date,
+        contractor_raw: contractor.clone(),
+        contractor,
+        contractor_count: optional_text(record, columns.contractor_count).and_then(|v| v.parse::<u32>().ok()),
+        contractor_members: Vec::new(),
+        planned_completion_date,
+        contract_duration_days,
+        funding_year,
+        source_id: 0,
+    }))
+}
+
+fn optional_text(record: &csv::StringRecord, idx: Option<usize>) -> Option<String> {
+    idx.and_then(|i| record.get(i))
+        .map(|v| v.trim())
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
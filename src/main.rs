@@ -1,657 +1,432 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::env;
 use std::error::Error;
 use std::io::{self, Write};
-use std::sync::Mutex;
-use chrono::{Datelike, NaiveDate};
-use once_cell::sync::Lazy;
-use num_format::{Locale, ToFormattedString};
-
-static APP_STATE: Lazy<Mutex<AppState>> = Lazy::new(|| Mutex::new(AppState::default()));
-
-#[derive(Default)]
-struct AppState {
-    projects: Vec<Project>,
-}
+use std::path::Path;
+use std::process::ExitCode;
+use hello_world::columns::ColumnMapping;
+use hello_world::contractor_clusters::{self, ClusterOptions};
+use hello_world::contractor_names::{self, ContractorAliases};
+use hello_world::reports::{
+    generate_reports, Dimension, JvAllocation, Normalization, RankingKey, RegionalOptions, ReliabilityMode, ReportKind, ReportOptions,
+    RiskTiers, SortOrder,
+};
+use hello_world::profile::{self, profile};
+use hello_world::{Dataset, DuplicatePolicy, FundingYears, LoadOptions};
+
+mod cli;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        return cli::run(&args);
+    }
 
-#[derive(Clone)]
-struct Project {
-    region: String,
-    main_island: String,
-    contractor: String,
-    approved_budget: f64,
-    contract_cost: f64,
-    type_of_work: String,
-    start_date: NaiveDate,
-    actual_completion_date: NaiveDate,
-    funding_year: i32,
+    match run_menu() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
 }
 
-// struct Project {
-//     main_island: String,
-//     region: String,
-//     province: String,
-//     legislative_district: String,
-//     municipality: String,
-//     district_engineering_office: String,
-//     project_id: String,
-//     project_name: String,
-//     work_type: String,
-//     approved_budget: f64,
-//     contract_cost: f64,
-//     start_date: NaiveDate,
-//     actual_completion_date: NaiveDate,
-//     contractor_name: String,
-//     contractor_count: i32,
-//     funding_year: i32,
-// }
-
-
-
-
-fn main() -> Result<(), Box<dyn Error>> {
+fn run_menu() -> Result<(), Box<dyn Error>> {
+    let out_dir = Path::new(".");
+    let mut dataset = Dataset::default();
+    let mut report_options = ReportOptions::default();
     loop {
         println!("Select Language Implementation:");
-        println!("[1] Load the file");
+        println!("[1] Load a file");
         println!("[2] Generate Reports");
+        println!("[3] List loaded files");
+        println!("[4] Unload a file");
+        println!("[5] Profile loaded data");
+        println!("[6] Suggest contractor aliases");
         print!("Enter Choice: ");
         io::stdout().flush().unwrap();
 
         let mut choice = String::new();
-        io::stdin().read_line(&mut choice)?;
+        if io::stdin().read_line(&mut choice)? == 0 {
+            // End of input: leave instead of re-prompting forever
+            println!();
+            return Ok(());
+        }
 
         match choice.trim() {
-            "1" => load_and_process_file()?,
-            "2" => generate_reports()?,
+            "1" => {
+                if let Err(e) = load_and_process_file(&mut dataset, out_dir) {
+                    println!("Load failed: {}", e);
+                }
+            }
+            "2" => {
+                if dataset.projects().is_empty() {
+                    println!("No data loaded. Please choose [1] Load a file first.");
+                } else {
+                    report_menu(&dataset, out_dir, &mut report_options)?;
+                }
+            }
+            "3" => list_loaded_files(&dataset),
+            "4" => unload_file(&mut dataset)?,
+            "5" => {
+                if dataset.projects().is_empty() {
+                    println!("No data loaded. Please choose [1] Load a file first.");
+                } else if let Err(e) = profile_dataset(&dataset, out_dir) {
+                    println!("Profile failed: {}", e);
+                }
+            }
+            "6" => {
+                if dataset.projects().is_empty() {
+                    println!("No data loaded. Please choose [1] Load a file first.");
+                } else if let Err(e) = suggest_aliases_menu(&dataset, out_dir) {
+                    println!("Alias suggestions failed: {}", e);
+                }
+            }
             _ => println!("Invalid choice. Please try again."),
         }
         println!();
     }
 }
 
-fn load_and_process_file() -> Result<(), Box<dyn Error>> {
-    print!("Enter CSV filename: ");
-    io::stdout().flush().unwrap();
-    let mut filename = String::new();
-    io::stdin().read_line(&mut filename)?;
-    let filename = filename.trim();
-
-    let mut rdr = csv::Reader::from_path(filename)?;
-    let headers = rdr.headers()?.clone();
-    let mut total_rows = 0;
-    let mut filtered_rows = 0;
-    let mut error_count = 0;
-
-    // Indexes for efficiency
-    let funding_year_idx = headers.iter().position(|h| h == "FundingYear");
-    let region_idx = headers.iter().position(|h| h == "Region");
-    let main_island_idx = headers.iter().position(|h| h == "MainIsland");
-    let contractor_idx = headers.iter().position(|h| h == "Contractor");
-    let type_of_work_idx = headers.iter().position(|h| h == "TypeOfWork");
-    let approved_budget_idx = headers.iter().position(|h| h == "ApprovedBudgetForContract");
-    let contract_cost_idx = headers.iter().position(|h| h == "ContractCost");
-    let start_date_idx = headers.iter().position(|h| h == "StartDate");
-    let actual_completion_idx = headers.iter().position(|h| h == "ActualCompletionDate");
-    
-    for result in rdr.records() {
-        total_rows += 1;
-        let record = match result {
-            Ok(r) => r,
-            Err(e) => {
-                eprintln!("Row {}: CSV parse error: {}", total_rows, e);
-                error_count += 1;
+// Report Selection submenu; loops until the user declines to come back.
+// Settings changed here are kept for the rest of the session.
+fn report_menu(dataset: &Dataset, out_dir: &Path, options: &mut ReportOptions) -> Result<(), Box<dyn Error>> {
+    loop {
+        println!();
+        println!("Report Selection:");
+        for (i, kind) in ReportKind::ALL.iter().enumerate() {
+            println!("[{}] {}", i + 1, kind.title());
+        }
+        println!("[{}] All reports", ReportKind::ALL.len() + 1);
+        println!("[{}] Report settings", ReportKind::ALL.len() + 2);
+        println!("[0] Back to main menu");
+
+        let choice = match prompt("Enter Choice: ") {
+            Ok(choice) => choice,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let kinds: Vec<ReportKind> = match choice.parse::<usize>() {
+            Ok(0) => return Ok(()),
+            Ok(n) if n <= ReportKind::ALL.len() => vec![ReportKind::ALL[n - 1]],
+            Ok(n) if n == ReportKind::ALL.len() + 1 => ReportKind::ALL.to_vec(),
+            Ok(n) if n == ReportKind::ALL.len() + 2 => {
+                match edit_report_settings(options) {
+                    Ok(updated) => *options = updated,
+                    Err(e) => println!("Settings unchanged: {}", e),
+                }
                 continue;
             }
-        };
-        // FundingYear validation and filter
-        let fy = funding_year_idx.and_then(|i| record.get(i));
-        let fy_num = match fy.and_then(|f| f.parse::<i32>().ok()) {
-            Some(y) if y >= 2021 && y <= 2023 => y,
-            Some(_) => continue,
-            None => {
-                eprintln!("Row {}: Invalid FundingYear: {:?}", total_rows, fy);
-                error_count += 1;
+            _ => {
+                println!("Invalid choice. Please try again.");
                 continue;
-            },
-        };
-
-        let region = match region_idx.and_then(|i| record.get(i)) {
-            Some(v) if !v.is_empty() => v.to_string(),
-            _ => { error_count += 1; continue; }
-        };
-        let main_island = match main_island_idx.and_then(|i| record.get(i)) {
-            Some(v) if !v.is_empty() => v.to_string(),
-            _ => { error_count += 1; continue; }
-        };
-
-        let contractor = match contractor_idx.and_then(|i| record.get(i)) {
-            Some(v) if !v.is_empty() => v.to_string(),
-            _ => { error_count += 1; continue; }
-        };
-
-        let type_of_work = match type_of_work_idx.and_then(|i| record.get(i)) {
-            Some(v) if !v.is_empty() => v.to_string(),
-            _ => { error_count += 1; continue; }
+            }
         };
+        generate_reports(dataset, out_dir, &kinds, options)?;
 
-        let approved_budget = match approved_budget_idx.and_then(|i| record.get(i)).and_then(|v| v.parse::<f64>().ok()) {
-            Some(v) => v,
-            None => { error_count += 1; continue; }
-        };
-        let contract_cost = match contract_cost_idx.and_then(|i| record.get(i)).and_then(|v| v.parse::<f64>().ok()) {
-            Some(v) => v,
-            None => { error_count += 1; continue; }
-        };
-        let start_date = match start_date_idx.and_then(|i| record.get(i)).and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok()) {
-            Some(d) => d,
-            None => { error_count += 1; continue; }
-        };
-        let actual_completion_date = match actual_completion_idx.and_then(|i| record.get(i)).and_then(|v| NaiveDate::parse_from_str(v, "%Y-%m-%d").ok()) {
-            Some(d) => d,
-            None => { error_count += 1; continue; }
+        println!();
+        let again = match prompt("Back to Report Selection (Y/N): ") {
+            Ok(answer) => answer,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
         };
-
-        filtered_rows += 1;
-        let mut state = APP_STATE.lock().unwrap();
-        state.projects.push(Project {
-            region,
-            main_island,
-            contractor,
-            type_of_work,
-            approved_budget,
-            contract_cost,
-            start_date,
-            actual_completion_date,
-            funding_year: fy_num,
-        });
-    }
-    println!("Processing dataset... ({} rows loaded, {} filtered for 2021-2023)", total_rows, filtered_rows);
-    if error_count > 0 {
-        println!("{} parse/validation errors encountered.", error_count);
+        if !again.eq_ignore_ascii_case("y") {
+            return Ok(());
+        }
     }
-    Ok(())
 }
 
-fn generate_reports() -> Result<(), Box<dyn Error>> {
-    let projects = {
-        let state = APP_STATE.lock().unwrap();
-        state.projects.clone()
-    };
-    if projects.is_empty() {
-        println!("No data loaded. Please choose [1] Load the file first.");
-        return Ok(());
+// Prompts for each setting, showing the current value as the default.
+// Returns the edited copy so a bad answer leaves the settings untouched.
+fn edit_report_settings(current: &ReportOptions) -> Result<ReportOptions, Box<dyn Error>> {
+    let mut options = current.clone();
+
+    println!("Report 1: Regional Summary");
+    let columns: Vec<&str> = options.regional.group_by.iter().map(|d| d.column()).collect();
+    let input = prompt(&format!(
+        "  Group by, comma separated (Region, MainIsland, Province, Municipality,\n  \
+         LegislativeDistrict, DistrictEngineeringOffice, TypeOfWork) [{}]: ",
+        columns.join(",")
+    ))?;
+    if !input.is_empty() {
+        options.regional.group_by = Dimension::parse_list(&input)?;
+    }
+    let bands: Vec<String> = options.regional.delay_bands.iter().map(|b| b.to_string()).collect();
+    let input = prompt(&format!("  Delay bands in days, comma separated [{}]: ", bands.join(",")))?;
+    if !input.is_empty() {
+        options.regional.delay_bands = RegionalOptions::parse_bands(&input)?;
+    }
+    let input = prompt(&format!(
+        "  EfficiencyScore normalization (minmax/rank/zscore/winsorized) [{}]: ",
+        options.regional.normalization.label()
+    ))?;
+    if !input.is_empty() {
+        options.regional.normalization =
+            Normalization::parse(&input).ok_or_else(|| format!("Unknown normalization: {:?}", input))?;
     }
 
-    println!("Generating reports...");
-
-    // Group by (Region, MainIsland)
-    let mut grouped: HashMap<(String, String), Vec<&Project>> = HashMap::new();
-    for p in &projects {
-        grouped.entry((p.region.clone(), p.main_island.clone()))
-            .or_default()
-            .push(p);
-    }
-
-    // Define Row (make sure this isn't defined elsewhere already)
-    #[derive(Clone)]
-    struct Row {
-        region: String,
-        main_island: String,
-        total_budget: f64,
-        median_savings: f64,
-        avg_delay: f64,
-        delay_over30_pct: f64,
-        efficiency_score: f64,
-    }
-
-    let mut rows: Vec<Row> = Vec::new();
-    const DELAY_THRESHOLD_DAYS: i64 = 30;
-
-    for ((region, main_island), items) in grouped {
-        let total_budget: f64 = items.iter().map(|p| p.approved_budget).sum();
-
-        // Compute savings (ApprovedBudgetForContract - ContractCost)
-        let mut savings: Vec<f64> = items.iter().map(|p| p.approved_budget - p.contract_cost).collect();
-        // Remove any NaN just in case (defensive)
-        savings.retain(|v| !v.is_nan());
-        savings.sort_by(|a, b| a.partial_cmp(b).unwrap());
-        let median_savings = if savings.is_empty() {
-            0.0
-        } else if savings.len() % 2 == 1 {
-            savings[savings.len() / 2]
-        } else {
-            let mid = savings.len() / 2;
-            (savings[mid - 1] + savings[mid]) / 2.0
-        };
+    println!("Report 2: Contractor Ranking");
+    let top_label = options.ranking.top_n.map_or("all".to_string(), |n| n.to_string());
+    let input = prompt(&format!("  Number of contractors to show, 0 for all [{}]: ", top_label))?;
+    if !input.is_empty() {
+        let n = input.parse::<usize>().map_err(|_| format!("Invalid number: {:?}", input))?;
+        options.ranking.top_n = if n == 0 { None } else { Some(n) };
+    }
+    let input = prompt(&format!("  Minimum projects per contractor [{}]: ", options.ranking.min_projects))?;
+    if !input.is_empty() {
+        options.ranking.min_projects = input.parse().map_err(|_| format!("Invalid number: {:?}", input))?;
+    }
+    let input = prompt(&format!(
+        "  Sort by (cost/projects/reliability/delay/savings) [{}]: ",
+        options.ranking.sort_key.column()
+    ))?;
+    if !input.is_empty() {
+        options.ranking.sort_key = RankingKey::parse(&input).ok_or_else(|| format!("Unknown sort key: {:?}", input))?;
+    }
+    let input = prompt(&format!("  Order (asc/desc) [{}]: ", options.ranking.order.label()))?;
+    if !input.is_empty() {
+        options.ranking.order = SortOrder::parse(&input).ok_or_else(|| format!("Unknown order: {:?}", input))?;
+    }
 
-        // Compute completion delays (days)
-        let delays: Vec<i64> = items.iter().map(|p| {
-            let d = (p.actual_completion_date - p.start_date).num_days();
-            if d < 0 { 0 } else { d }
-        }).collect();
-
-        let avg_delay = if delays.is_empty() { 0.0 } else { (delays.iter().sum::<i64>() as f64) / (delays.len() as f64) };
-        let delay_over30_count = delays.iter().filter(|d| **d > DELAY_THRESHOLD_DAYS).count();
-        let delay_over30_pct = if delays.is_empty() { 0.0 } else { (delay_over30_count as f64) * 100.0 / (delays.len() as f64) };
-
-        // Compute efficiency score = (median_savings / avg_delay) * 100
-        let raw_efficiency = if avg_delay > 0.0 {
-            (median_savings / avg_delay) * 100.0
-        } else {
-            0.0
-        };
+    let input = prompt(&format!(
+        "  Joint ventures (combined/equal/full) [{}]: ",
+        options.ranking.jv_allocation.label()
+    ))?;
+    if !input.is_empty() {
+        options.ranking.jv_allocation =
+            JvAllocation::parse(&input).ok_or_else(|| format!("Unknown JV allocation: {:?}", input))?;
+    }
 
-        rows.push(Row {
-            region,
-            main_island,
-            total_budget,
-            median_savings,
-            avg_delay,
-            delay_over30_pct,
-            efficiency_score: raw_efficiency,
-        });
-    }
-
-
-    // Normalize efficiency scores to 0–100 range
-    if let (Some(min), Some(max)) = (
-        rows.iter().map(|r| r.efficiency_score).reduce(f64::min),
-        rows.iter().map(|r| r.efficiency_score).reduce(f64::max),
-    ) {
-        for r in &mut rows {
-            if max > min {
-                r.efficiency_score = ((r.efficiency_score - min) / (max - min)) * 100.0;
-            } else {
-                r.efficiency_score = 100.0; // all same values
+    let reliability = &mut options.ranking.reliability;
+    let input = prompt(&format!("  Reliability scoring (formula/percentile) [{}]: ", reliability.mode.label()))?;
+    if !input.is_empty() {
+        reliability.mode =
+            ReliabilityMode::parse(&input).ok_or_else(|| format!("Unknown reliability mode: {:?}", input))?;
+    }
+    if reliability.mode == ReliabilityMode::Formula {
+        let input = prompt(&format!("  Delay horizon in days [{}]: ", reliability.delay_horizon_days))?;
+        if !input.is_empty() {
+            reliability.delay_horizon_days = weight(&input)?;
+            if reliability.delay_horizon_days == 0.0 {
+                return Err("Delay horizon must be greater than 0".into());
+            }
+        }
+        let input = prompt(&format!("  Savings ratio for full savings score [{}]: ", reliability.savings_target))?;
+        if !input.is_empty() {
+            reliability.savings_target = weight(&input)?;
+            if reliability.savings_target == 0.0 {
+                return Err("Savings target must be greater than 0".into());
             }
         }
     }
-
-    // Sort descending by EfficiencyScore
-    rows.sort_by(|a, b| b.efficiency_score.partial_cmp(&a.efficiency_score).unwrap());
-
-    use num_format::{Locale, ToFormattedString};
-
-fn format_comma_float(val: f64) -> String {
-    // Handles negatives and formats with commas + 2 decimal places
-    let sign = if val.is_sign_negative() { "-" } else { "" };
-    let abs_val = val.abs();
-    let whole = abs_val.trunc() as i64;
-    let fraction = (abs_val.fract() * 100.0).round() as u8;
-    format!("{}{}.{:02}", sign, whole.to_formatted_string(&Locale::en), fraction)
-}
-
-
-    // Display Report 1
-    println!();
-    println!("Report 1: Regional Flood Mitigation Efficiency Summary");
-    println!("(Aggregated by Region & MainIsland; 2021–2023 Projects)");
-    println!();
-
-    // Header with fixed widths
-    println!(
-        "| {:<40} | {:<10} | {:>18} | {:>15} | {:>13} | {:>12} | {:>17} |",
-        "Region", "MainIsland", "TotalBudget", "MedianSavings", "AvgDelayDays", "Delay>30Pct", "EfficiencyScore"
-    );
-    println!("{}", "-".repeat(146));
-
-    // Single loop: print each row once
-    for r in &rows {
-        println!(
-            "| {:<40} | {:<10} | {:>18} | {:>15} | {:>13.2} | {:>12.1} | {:>17.2} |",
-            r.region.trim(),
-            r.main_island.trim(),
-            format_comma_float(r.total_budget),
-            format_comma_float(r.median_savings),
-            r.avg_delay,
-            r.delay_over30_pct,
-            r.efficiency_score
-        );
+    let input = prompt(&format!("  Delay weight [{}]: ", reliability.delay_weight))?;
+    if !input.is_empty() {
+        reliability.delay_weight = weight(&input)?;
+    }
+    let input = prompt(&format!("  Savings weight [{}]: ", reliability.savings_weight))?;
+    if !input.is_empty() {
+        reliability.savings_weight = weight(&input)?;
+    }
+    reliability.check_weights()?;
+    let tiers = reliability.tiers;
+    let input = prompt(&format!(
+        "  Risk cut-offs Low,Medium,High [{},{},{}]: ",
+        tiers.low, tiers.medium, tiers.high
+    ))?;
+    if !input.is_empty() {
+        reliability.tiers = RiskTiers::parse(&input)?;
     }
 
-    println!();
-    println!("Full table exported to report_1_regional_summary.csv");
-
-
-
-    // Export CSV (sorted)
-    let mut wtr = csv::Writer::from_path("report_1_regional_summary.csv")?;
-    wtr.write_record([
-        "Region",
-        "MainIsland",
-        "TotalBudget",
-        "MedianSavings",
-        "AvgDelayDays",
-        "DelayOver30Pct",
-        "EfficiencyScore",
-    ])?;
-    for r in rows {
-        wtr.write_record(&[
-            r.region,
-            r.main_island,
-            format!("{:.2}", r.total_budget),
-            format!("{:.2}", r.median_savings),
-            format!("{:.2}", r.avg_delay),
-            format!("{:.1}", r.delay_over30_pct),
-            format!("{:.2}", r.efficiency_score),
-        ])?;
-    }
-    wtr.flush()?;
-
-    // =============================
-    // Report 2: Contractor Ranking
-    // =============================
-    println!();
-    println!("Report 2: Top Contractors Performance Ranking");
-    println!("(Top 15 by TotalCost, >=5 Projects)");
-    println!();
+    Ok(options)
+}
 
-    // Group by Contractor
-    let mut contractor_group: HashMap<String, Vec<&Project>> = HashMap::new();
-    for p in &projects {
-        contractor_group.entry(p.contractor.clone()).or_default().push(p);
+fn weight(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(n) if n.is_finite() && n >= 0.0 => Ok(n),
+        _ => Err(format!("Invalid number: {:?}", input)),
     }
+}
 
-    #[derive(Debug)]
-    struct ContractorRow {
-        contractor: String,
-        total_cost: f64,
-        num_projects: usize,
-        avg_delay: f64,
-        total_savings: f64,
-        reliability_index: f64,
-        risk_flag: String,
+fn prompt(message: &str) -> io::Result<String> {
+    print!("{}", message);
+    io::stdout().flush()?;
+    let mut input = String::new();
+    if io::stdin().read_line(&mut input)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of input"));
     }
+    Ok(input.trim().to_string())
+}
 
-    let mut contractor_rows: Vec<ContractorRow> = Vec::new();
-
-    for (contractor, items) in contractor_group {
-        if items.len() < 5 {
-            continue;
-        }
+fn load_and_process_file(dataset: &mut Dataset, out_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let filename = prompt("Enter CSV filename: ")?;
+    let mut options = LoadOptions::default();
 
-        let total_cost: f64 = items.iter().map(|p| p.contract_cost).sum();
-        let total_savings: f64 = items.iter().map(|p| p.approved_budget - p.contract_cost).sum();
+    let years_input = prompt("Enter funding years (e.g. 2021-2023 or 2018,2020,2024) [2021-2023]: ")?;
+    if !years_input.is_empty() {
+        options.funding_years = FundingYears::parse(&years_input)?;
+    }
 
-        let delays: Vec<i64> = items.iter()
-            .map(|p| (p.actual_completion_date - p.start_date).num_days().max(0))
+    let formats_input = prompt("Enter date formats in order, separated by ';' [default list]: ")?;
+    if !formats_input.is_empty() {
+        options.date_formats = formats_input
+            .split(';')
+            .map(str::trim)
+            .filter(|f| !f.is_empty())
+            .map(str::to_string)
             .collect();
+    }
 
-        let avg_delay = if delays.is_empty() {
-            0.0
-        } else {
-            delays.iter().sum::<i64>() as f64 / delays.len() as f64
-        };
+    let excel_input = prompt("Accept Excel serial dates? (Y/N) [N]: ")?;
+    options.excel_serial_dates = excel_input.eq_ignore_ascii_case("y");
 
-        let mut reliability_index = (1.0 - (avg_delay / 90.0)) * (total_savings / total_cost) * 100.0;
-        if reliability_index > 100.0 {
-            reliability_index = 100.0;
-        } else if reliability_index < 0.0 {
-            reliability_index = 0.0;
-        }
+    let mapping_input = prompt("Enter column mapping file (JSON) [none]: ")?;
+    if !mapping_input.is_empty() {
+        options.column_mapping = ColumnMapping::from_json_file(&mapping_input)?;
+    }
 
-        let risk_flag = if reliability_index < 50.0 {
-            "High Risk".to_string()
-        } else {
-            "Low Risk".to_string()
-        };
+    let aliases_input = prompt("Enter contractor alias file (CSV with Alias,Canonical) [none]: ")?;
+    if !aliases_input.is_empty() {
+        options.contractor_aliases = ContractorAliases::from_csv_file(&aliases_input)?;
+    }
 
-        contractor_rows.push(ContractorRow {
-            contractor,
-            total_cost,
-            num_projects: items.len(),
-            avg_delay,
-            total_savings,
-            reliability_index,
-            risk_flag,
-        });
-    }
-
-    // Sort by descending total cost
-    contractor_rows.sort_by(|a, b| b.total_cost.partial_cmp(&a.total_cost).unwrap());
-
-    // Keep top 15
-    let top_rows: Vec<_> = contractor_rows.into_iter().take(5000).collect();
-
-    // Print formatted table
-    // Helper: truncate long contractor names for display
-    fn truncate_name(name: &str, max_len: usize) -> String {
-        if name.len() > max_len {
-            format!("{}...", &name[..max_len - 3])
-        } else {
-            name.to_string()
-        }
+    let policy_input = prompt("On duplicate ProjectId keep first, keep last or merge? (F/L/M) [F]: ")?;
+    if !policy_input.is_empty() {
+        options.duplicate_policy = DuplicatePolicy::parse(&policy_input)
+            .ok_or_else(|| format!("Unknown duplicate policy: {:?}", policy_input))?;
     }
 
-    // Print formatted table
-    println!(
-        "| {:<4} | {:<45} | {:<18} | {:<12} | {:<10} | {:<16} | {:<18} | {:<10} |",
-        "Rank", "Contractor", "TotalCost", "NumProjects", "AvgDelay", "TotalSavings", "ReliabilityIndex", "RiskFlag"
-    );
-    println!("{}", "-".repeat(165));
+    load_file(dataset, &filename, &options, out_dir)
+}
 
-    for (i, r) in top_rows.iter().enumerate() {
+// Reads one CSV file into the dataset and prints the load summary.
+// Rejected rows of every loaded file are written to rejected_rows.csv and
+// merged contractor spellings to contractor_merges.csv in out_dir.
+fn load_file(dataset: &mut Dataset, filename: &str, options: &LoadOptions, out_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let report = dataset.load_file(filename, options)?;
+    report.print();
+    if dataset.rejected_row_count() > 0 {
+        let path = out_dir.join("rejected_rows.csv");
+        dataset.write_rejected_rows(&path)?;
         println!(
-            "| {:<4} | {:<45} | {:>18} | {:>12} | {:>10.1} | {:>16} | {:>18.2} | {:<10} |",
-            i + 1,
-            truncate_name(&r.contractor, 45),
-            format_comma_float(r.total_cost),
-            r.num_projects,
-            r.avg_delay,
-            format_comma_float(r.total_savings),
-            r.reliability_index,
-            r.risk_flag
+            "{} rejected rows from all loaded files exported to {}",
+            dataset.rejected_row_count(),
+            path.display()
         );
     }
 
+    let merges = dataset.contractor_merges();
+    if !merges.is_empty() {
+        let canonical = merges.iter().map(|m| m.canonical.as_str()).collect::<HashSet<_>>();
+        println!(
+            "{} contractor spellings merged into {} canonical names",
+            merges.len(),
+            canonical.len()
+        );
+        let path = out_dir.join("contractor_merges.csv");
+        contractor_names::write_merges_csv(&merges, &path)?;
+        println!("Contractor name merges exported to {}", path.display());
+    }
+    Ok(())
+}
 
+// Prints the data-quality profile and exports it as CSV (columns and checks
+// in separate files) and JSON in out_dir
+fn profile_dataset(dataset: &Dataset, out_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let profile = profile(dataset);
+    profile.print();
+    let csv_path = out_dir.join(profile::CSV_FILE);
+    let checks_path = out_dir.join(profile::CHECKS_CSV_FILE);
+    let json_path = out_dir.join(profile::JSON_FILE);
+    profile.write_csv(&csv_path)?;
+    profile.write_checks_csv(&checks_path)?;
+    profile.write_json(&json_path)?;
     println!();
-    println!("Full table exported to report_2_contractor_ranking.csv");
-
-    // Export CSV
-    let mut wtr2 = csv::Writer::from_path("report_2_contractor_ranking.csv")?;
-    wtr2.write_record([
-        "Rank",
-        "Contractor",
-        "TotalCost",
-        "NumProjects",
-        "AvgDelay",
-        "TotalSavings",
-        "ReliabilityIndex",
-        "RiskFlag",
-    ])?;
-
-    for (i, r) in top_rows.iter().enumerate() {
-        wtr2.write_record(&[
-            (i + 1).to_string(),
-            r.contractor.clone(),
-            format!("{:.2}", r.total_cost),
-            r.num_projects.to_string(),
-            format!("{:.2}", r.avg_delay),
-            format!("{:.2}", r.total_savings),
-            format!("{:.2}", r.reliability_index),
-            r.risk_flag.clone(),
-        ])?;
-    }
-    wtr2.flush()?;
-
-    // =============================
-    // Report 3: Annual Project Type Cost Overrun Trends
-    // =============================
-    println!();
-    println!("Report 3: Annual Project Type Cost Overrun Trends");
-    println!("(Grouped by FundingYear and TypeOfWork)");
-    println!();
+    println!(
+        "Profile exported to {}, {} and {}",
+        csv_path.display(),
+        checks_path.display(),
+        json_path.display()
+    );
+    Ok(())
+}
 
-    use std::fs::File;
-    use serde_json::json;
-
-    // Group by (FundingYear, TypeOfWork)
-    let mut grouped3: HashMap<(i32, String), Vec<&Project>> = HashMap::new();
-    for p in &projects {
-        grouped3
-            .entry((p.funding_year, p.type_of_work.clone()))
-            .or_default()
-            .push(p);
-    }
-
-    #[derive(Clone)]
-    struct Row3 {
-        funding_year: i32,
-        type_of_work: String,
-        total_projects: usize,
-        avg_savings: f64,
-        overrun_rate: f64,
-        yoy_change: f64,
-    }
-
-    let mut rows3: Vec<Row3> = Vec::new();
-
-    // Compute 2021 baseline averages
-    let mut baseline_savings: HashMap<String, f64> = HashMap::new();
-    for ((year, work_type), items) in &grouped3 {
-        if *year == 2021 {
-            let avg_savings = if items.is_empty() {
-                0.0
-            } else {
-                items.iter()
-                    .map(|p| p.approved_budget - p.contract_cost)
-                    .sum::<f64>() / (items.len() as f64)
-            };
-            baseline_savings.insert(work_type.clone(), avg_savings);
-        }
+fn suggest_aliases_menu(dataset: &Dataset, out_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let mut options = ClusterOptions::default();
+    let input = prompt(&format!("Minimum name similarity, 0 to 1 [{}]: ", options.min_similarity))?;
+    if !input.is_empty() {
+        options.min_similarity = fraction(&input)?;
     }
-
-    // Fill per (year, type_of_work)
-    for ((year, work_type), items) in grouped3 {
-        let total_projects = items.len();
-
-        let savings: Vec<f64> = items.iter().map(|p| p.approved_budget - p.contract_cost).collect();
-        let avg_savings = if savings.is_empty() {
-            0.0
-        } else {
-            savings.iter().sum::<f64>() / (savings.len() as f64)
-        };
-
-        let overrun_rate = if savings.is_empty() {
-            0.0
-        } else {
-            let negative_count = savings.iter().filter(|s| **s < 0.0).count();
-            (negative_count as f64) * 100.0 / (savings.len() as f64)
-        };
-
-        // Compute YoY change from 2021 baseline
-        let baseline = baseline_savings.get(&work_type).cloned().unwrap_or(0.0);
-        let yoy_change = if baseline.abs() < f64::EPSILON {
-            0.0
-        } else {
-            ((avg_savings - baseline) / baseline) * 100.0
-        };
-
-        rows3.push(Row3 {
-            funding_year: year,
-            type_of_work: work_type,
-            total_projects,
-            avg_savings,
-            overrun_rate,
-            yoy_change,
-        });
+    let input = prompt(&format!("Minimum word overlap, 0 to 1 [{}]: ", options.min_token_overlap))?;
+    if !input.is_empty() {
+        options.min_token_overlap = fraction(&input)?;
     }
+    suggest_contractor_aliases(dataset, &options, out_dir)
+}
 
-    // Sort by year then work type
-    rows3.sort_by(|a, b| {
-        a.funding_year.cmp(&b.funding_year)
-            .then_with(|| a.type_of_work.cmp(&b.type_of_work))
-    });
+fn fraction(input: &str) -> Result<f64, String> {
+    match input.parse::<f64>() {
+        Ok(n) if (0.0..=1.0).contains(&n) => Ok(n),
+        _ => Err(format!("Expected a number from 0 to 1, got {:?}", input)),
+    }
+}
 
-    // Print formatted table
-    // Print formatted table
+// Prints contractor names that look like the same firm and exports them for
+// review as an alias file in out_dir
+fn suggest_contractor_aliases(dataset: &Dataset, options: &ClusterOptions, out_dir: &Path) -> Result<(), Box<dyn Error>> {
+    let suggestions = contractor_clusters::suggest_aliases(dataset.projects(), options);
+    contractor_clusters::print(&suggestions, options);
+    let path = out_dir.join(contractor_clusters::CSV_FILE);
+    contractor_clusters::write_csv(&suggestions, &path)?;
+    println!();
     println!(
-        "{:<12} | {:<40} | {:>15} | {:>15} | {:>12} | {:>12} |",
-        "FundingYear", "TypeOfWork", "TotalProjects", "AvgSavings", "OverrunRate", "YoYChange"
+        "{} suggestions exported to {}; mark Approved as yes and load it as the contractor alias file",
+        suggestions.len(),
+        path.display()
     );
-    println!("{}", "-".repeat(118));
-
-    for r in &rows3 {
-        let type_of_work_display = if r.type_of_work.len() > 40 {
-            format!("{}...", &r.type_of_work[..37])
-        } else {
-            r.type_of_work.trim().to_string()
-        };
+    Ok(())
+}
 
+fn list_loaded_files(dataset: &Dataset) {
+    if dataset.sources().is_empty() {
+        println!("No files loaded.");
+        return;
+    }
+    println!(
+        "| {:>3} | {:<40} | {:<16} | {:>10} | {:>10} | {:<10} |",
+        "#", "File", "FundingYears", "Projects", "Duplicates", "Policy"
+    );
+    println!("{}", "-".repeat(108));
+    for source in dataset.sources() {
         println!(
-            "| {:<12} | {:<40} | {:>15} | {:>15.2} | {:>11.1}% | {:>11.1}% |",
-            r.funding_year,
-            type_of_work_display,
-            r.total_projects,
-            r.avg_savings,
-            r.overrun_rate,
-            r.yoy_change
+            "| {:>3} | {:<40} | {:<16} | {:>10} | {:>10} | {:<10} |",
+            source.id,
+            source.path,
+            source.funding_years.label(),
+            dataset.project_count(source.id),
+            source.duplicates.total(),
+            source.policy.label()
         );
     }
+    println!("Total projects: {}", dataset.projects().len());
+}
 
-
-    println!();
-    println!("(Full table exported to report3_annual_trends.csv)");
-
-    // Export CSV
-    let mut wtr3 = csv::Writer::from_path("report3_annual_trends.csv")?;
-    wtr3.write_record([
-        "FundingYear",
-        "TypeOfWork",
-        "TotalProjects",
-        "AvgSavings",
-        "OverrunRate",
-        "YoYChange",
-    ])?;
-    for r in &rows3 {
-        wtr3.write_record(&[
-            r.funding_year.to_string(),
-            r.type_of_work.clone(),
-            r.total_projects.to_string(),
-            format!("{:.2}", r.avg_savings),
-            format!("{:.2}", r.overrun_rate),
-            format!("{:.2}", r.yoy_change),
-        ])?;
-    }
-    wtr3.flush()?;
-
-    // =============================
-    // Summary Stats
-    // =============================
-    let total_projects = projects.len();
-    let total_contractors = projects.iter().map(|p| p.contractor.clone()).collect::<std::collections::HashSet<_>>().len();
-    let total_provinces = projects.iter().map(|p| p.region.clone()).collect::<std::collections::HashSet<_>>().len();
-
-    let total_savings: f64 = projects.iter().map(|p| p.approved_budget - p.contract_cost).sum();
-    let global_avg_delay: f64 = if projects.is_empty() {
-        0.0
-    } else {
-        projects
-            .iter()
-            .map(|p| (p.actual_completion_date - p.start_date).num_days() as f64)
-            .sum::<f64>()
-            / (projects.len() as f64)
+fn unload_file(dataset: &mut Dataset) -> Result<(), Box<dyn Error>> {
+    list_loaded_files(dataset);
+    if dataset.sources().is_empty() {
+        return Ok(());
+    }
+    let input = prompt("Enter file # to unload: ")?;
+    let Ok(id) = input.parse::<usize>() else {
+        println!("Invalid file number: {:?}", input);
+        return Ok(());
     };
-
-    let summary = json!({
-        "total_projects": total_projects,
-        "total_contractors": total_contractors,
-        "total_provinces": total_provinces,
-        "global_avg_delay": global_avg_delay,
-        "total_savings": total_savings
-    });
-
-    let file = File::create("summary.json")?;
-    serde_json::to_writer_pretty(file, &summary)?;
-
-    println!();
-    println!("Summary Stats (summary.json):");
-    println!("{}", summary.to_string());
-    println!();
-    println!("Back to Report Selection (Y/N):");
-
-
-
+    match dataset.unload(id) {
+        Some(source) => println!(
+            "Unloaded {}; dataset now holds {} projects",
+            source.path,
+            dataset.projects().len()
+        ),
+        None => println!("No loaded file #{}", id),
+    }
     Ok(())
 }
-
@@ -0,0 +1,240 @@
+// Per-project measures and the statistics built on them. Every report and
+// summary.json goes through these so their numbers reconcile.
+use chrono::{Duration, NaiveDate};
+use crate::project::Project;
+
+// ApprovedBudgetForContract - ContractCost; negative for an overrun
+pub fn savings(project: &Project) -> f64 {
+    project.approved_budget - project.contract_cost
+}
+
+// ContractCost - ApprovedBudgetForContract when positive, otherwise 0
+pub fn overrun(project: &Project) -> f64 {
+    (project.contract_cost - project.approved_budget).max(0.0)
+}
+
+// Savings as a share of contract cost; 0 when there is no cost to compare with
+pub fn savings_ratio(savings: f64, contract_cost: f64) -> f64 {
+    if contract_cost > 0.0 { savings / contract_cost } else { 0.0 }
+}
+
+// Days from StartDate to ActualCompletionDate; negative when the dates are
+// out of order in the source
+pub fn duration_days(project: &Project) -> i64 {
+    (project.actual_completion_date - project.start_date).num_days()
+}
+
+// PlannedCompletionDate, else StartDate + ContractDurationDays
+pub fn planned_completion_date(project: &Project) -> Option<NaiveDate> {
+    project
+        .planned_completion_date
+        .or_else(|| project.contract_duration_days.map(|days| project.start_date + Duration::days(days)))
+}
+
+// Days finished past the planned completion date; 0 when on time or early
+pub fn slippage_days(project: &Project) -> Option<i64> {
+    planned_completion_date(project).map(|planned| (project.actual_completion_date - planned).num_days().max(0))
+}
+
+// Slippage when the planned completion is known, otherwise the duration
+// (out-of-order dates count as no delay)
+pub fn delay_days(project: &Project) -> i64 {
+    slippage_days(project).unwrap_or_else(|| duration_days(project).max(0))
+}
+
+// Which measure delay_days used for a set of projects
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DelayBasis {
+    Slippage,
+    Duration,
+    // Some projects measured each way
+    Mixed,
+}
+
+impl DelayBasis {
+    pub fn of<'a>(projects: impl IntoIterator<Item = &'a Project>) -> Self {
+        let (mut slippage, mut duration) = (false, false);
+        for p in projects {
+            if planned_completion_date(p).is_some() {
+                slippage = true;
+            } else {
+                duration = true;
+            }
+        }
+        match (slippage, duration) {
+            (true, false) => DelayBasis::Slippage,
+            (true, true) => DelayBasis::Mixed,
+            _ => DelayBasis::Duration,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DelayBasis::Slippage => "slippage",
+            DelayBasis::Duration => "duration",
+            DelayBasis::Mixed => "mixed",
+        }
+    }
+}
+
+// Printed under each report title that uses delay
+pub const DELAY_NOTE: &str =
+    "Delay = days past planned completion (slippage) where known, otherwise total duration; see DelayBasis";
+
+// Arithmetic mean; 0 for no values
+pub fn mean(values: &[f64]) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().sum::<f64>() / values.len() as f64
+    }
+}
+
+// Mean where each value counts in proportion to its weight; 0 when the
+// weights sum to 0
+pub fn weighted_mean(values: &[(f64, f64)]) -> f64 {
+    let total_weight: f64 = values.iter().map(|(_, w)| w).sum();
+    if total_weight <= 0.0 {
+        0.0
+    } else {
+        values.iter().map(|(v, w)| v * w).sum::<f64>() / total_weight
+    }
+}
+
+pub fn median(values: &[f64]) -> f64 {
+    percentile(values, 0.5)
+}
+
+// q in 0.0..=1.0, interpolating linearly between closest ranks. NaNs are
+// ignored; 0 for no values.
+pub fn percentile(values: &[f64], q: f64) -> f64 {
+    let mut sorted: Vec<f64> = values.iter().copied().filter(|v| !v.is_nan()).collect();
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let pos = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let lower = pos.floor() as usize;
+    let upper = pos.ceil() as usize;
+    let weight = pos - lower as f64;
+    sorted[lower] * (1.0 - weight) + sorted[upper] * weight
+}
+
+// Mid-rank percentile of value within values, 0.0 (lowest) to 1.0 (highest);
+// 0.5 when there is nothing to compare with
+pub fn percentile_rank(values: &[f64], value: f64) -> f64 {
+    if values.len() < 2 {
+        return 0.5;
+    }
+    let below = values.iter().filter(|v| **v < value).count() as f64;
+    let equal = values.iter().filter(|v| **v == value).count() as f64;
+    (below + (equal - 1.0) / 2.0) / (values.len() as f64 - 1.0)
+}
+
+// Share of values matching the predicate, 0–100
+pub fn share_pct<T>(values: &[T], predicate: impl Fn(&T) -> bool) -> f64 {
+    if values.is_empty() {
+        0.0
+    } else {
+        values.iter().filter(|v| predicate(v)).count() as f64 * 100.0 / values.len() as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::test_project;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn savings_and_overrun() {
+        let p = Project { approved_budget: 1_000.0, contract_cost: 1_200.0, ..test_project() };
+        assert_eq!(savings(&p), -200.0);
+        assert_eq!(overrun(&p), 200.0);
+        let p = Project { approved_budget: 1_000.0, contract_cost: 900.0, ..test_project() };
+        assert_eq!(overrun(&p), 0.0);
+        assert_eq!(savings_ratio(100.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn delay_uses_slippage_when_planned_date_is_known() {
+        let p = Project {
+            start_date: date(2022, 1, 1),
+            actual_completion_date: date(2022, 3, 1),
+            ..test_project()
+        };
+        assert_eq!(delay_days(&p), 59);
+        assert_eq!(slippage_days(&p), None);
+
+        let planned = Project { planned_completion_date: Some(date(2022, 2, 20)), ..p.clone() };
+        assert_eq!(delay_days(&planned), 9);
+        let by_duration = Project { contract_duration_days: Some(50), ..p.clone() };
+        assert_eq!(planned_completion_date(&by_duration), Some(date(2022, 2, 20)));
+        assert_eq!(delay_days(&by_duration), 9);
+    }
+
+    #[test]
+    fn delay_is_clamped_at_zero() {
+        let early = Project {
+            start_date: date(2022, 1, 1),
+            actual_completion_date: date(2022, 2, 1),
+            planned_completion_date: Some(date(2022, 3, 1)),
+            ..test_project()
+        };
+        assert_eq!(slippage_days(&early), Some(0));
+        let out_of_order = Project {
+            start_date: date(2022, 3, 1),
+            actual_completion_date: date(2022, 2, 1),
+            ..test_project()
+        };
+        assert_eq!(duration_days(&out_of_order), -28);
+        assert_eq!(delay_days(&out_of_order), 0);
+    }
+
+    #[test]
+    fn percentile_interpolates_and_ignores_nan() {
+        let values = [40.0, 10.0, 30.0, 20.0];
+        assert_eq!(percentile(&values, 0.0), 10.0);
+        assert_eq!(percentile(&values, 0.5), 25.0);
+        assert_eq!(percentile(&values, 0.25), 17.5);
+        assert_eq!(percentile(&values, 1.0), 40.0);
+        assert_eq!(median(&[1.0, f64::NAN, 3.0]), 2.0);
+        assert_eq!(percentile(&[], 0.5), 0.0);
+    }
+
+    #[test]
+    fn percentile_rank_uses_mid_rank_for_ties() {
+        let values = [1.0, 2.0, 2.0, 3.0];
+        assert_eq!(percentile_rank(&values, 1.0), 0.0);
+        assert_eq!(percentile_rank(&values, 2.0), 0.5);
+        assert_eq!(percentile_rank(&values, 3.0), 1.0);
+        assert_eq!(percentile_rank(&[5.0, 5.0], 5.0), 0.5);
+        assert_eq!(percentile_rank(&[5.0], 5.0), 0.5);
+    }
+
+    #[test]
+    fn weighted_mean_handles_zero_weights() {
+        assert_eq!(weighted_mean(&[(1.0, 3.0), (0.0, 1.0)]), 0.75);
+        assert_eq!(weighted_mean(&[(1.0, 0.0), (0.5, 2.0)]), 0.5);
+        assert_eq!(weighted_mean(&[(1.0, 0.0), (0.5, 0.0)]), 0.0);
+    }
+
+    #[test]
+    fn mean_and_share_of_empty_input() {
+        assert_eq!(mean(&[]), 0.0);
+        assert_eq!(share_pct::<f64>(&[], |_| true), 0.0);
+        assert_eq!(share_pct(&[1, 2, 3, 4], |v| *v > 3), 25.0);
+    }
+
+    #[test]
+    fn delay_basis_of_projects() {
+        let planned = Project { planned_completion_date: Some(date(2022, 4, 1)), ..test_project() };
+        let unplanned = test_project();
+        assert_eq!(DelayBasis::of([&planned]), DelayBasis::Slippage);
+        assert_eq!(DelayBasis::of([&unplanned]), DelayBasis::Duration);
+        assert_eq!(DelayBasis::of([&planned, &unplanned]), DelayBasis::Mixed);
+    }
+}
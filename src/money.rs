@@ -0,0 +1,127 @@
+// Currency markers stripped from either end of a value, compared case-insensitively
+const CURRENCY_MARKERS: &[&str] = &["php", "₱"];
+
+// Parses ApprovedBudgetForContract / ContractCost values such as
+// "₱1,234,567.89", "PHP 12,000" or "(5,000.00)", counting how many
+// values needed cleaning before they were valid numbers.
+#[derive(Default)]
+pub struct MoneyParser {
+    normalized: usize,
+}
+
+impl MoneyParser {
+    pub fn parse(&mut self, raw: &str) -> Option<f64> {
+        let (value, normalized) = parse_money(raw)?;
+        if normalized {
+            self.normalized += 1;
+        }
+        Some(value)
+    }
+
+    pub fn normalized_count(&self) -> usize {
+        self.normalized
+    }
+}
+
+// Returns the amount and whether the raw text needed cleaning first
+fn parse_money(raw: &str) -> Option<(f64, bool)> {
+    if let Ok(value) = raw.parse::<f64>()
+        && value.is_finite()
+    {
+        return Some((value, false));
+    }
+
+    let mut text = raw.trim();
+    if text.is_empty() {
+        return None;
+    }
+
+    // Accounting negatives: "(5,000.00)"
+    let mut negative = false;
+    if let Some(inner) = text.strip_prefix('(').and_then(|t| t.strip_suffix(')')) {
+        negative = true;
+        text = inner.trim();
+    }
+    if let Some(rest) = text.strip_prefix('-') {
+        negative = !negative;
+        text = rest.trim();
+    }
+    text = strip_currency(text);
+    // Sign written after the currency marker: "₱-5,000"
+    if let Some(rest) = text.strip_prefix('-') {
+        negative = !negative;
+        text = rest.trim();
+    }
+
+    let digits: String = text.chars().filter(|c| *c != ',').collect();
+    if digits.is_empty() || !digits.chars().all(|c| c.is_ascii_digit() || c == '.') {
+        return None;
+    }
+    let amount = digits.parse::<f64>().ok().filter(|v| v.is_finite())?;
+    Some((if negative { -amount } else { amount }, true))
+}
+
+fn strip_currency(mut text: &str) -> &str {
+    for marker in CURRENCY_MARKERS {
+        if let Some(prefix) = text.get(..marker.len())
+            && prefix.eq_ignore_ascii_case(marker)
+        {
+            text = text[marker.len()..].trim_start();
+        }
+        if let Some(start) = text.len().checked_sub(marker.len())
+            && let Some(suffix) = text.get(start..)
+            && suffix.eq_ignore_ascii_case(marker)
+        {
+            text = text[..start].trim_end();
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn plain_numbers_are_not_counted_as_normalized() {
+        assert_eq!(parse_money("1234.5"), Some((1234.5, false)));
+    }
+
+    #[test]
+    fn strips_currency_markers_and_separators() {
+        assert_eq!(parse_money("₱1,234,567.89"), Some((1_234_567.89, true)));
+        assert_eq!(parse_money("PHP 12,000"), Some((12_000.0, true)));
+        assert_eq!(parse_money("12,000 php"), Some((12_000.0, true)));
+        assert_eq!(parse_money("  5,000.00  "), Some((5_000.0, true)));
+    }
+
+    #[test]
+    fn accounting_negatives() {
+        assert_eq!(parse_money("(5,000.00)"), Some((-5_000.0, true)));
+        assert_eq!(parse_money("(₱5,000.00) "), Some((-5_000.0, true)));
+        assert_eq!(parse_money("₱-5,000"), Some((-5_000.0, true)));
+    }
+
+    #[test]
+    fn rejects_text() {
+        assert_eq!(parse_money(""), None);
+        assert_eq!(parse_money("abc"), None);
+        assert_eq!(parse_money("PHP"), None);
+        assert_eq!(parse_money("1,2a3"), None);
+    }
+
+    #[test]
+    fn rejects_overflowing_amounts() {
+        let huge = format!("PHP {}", "9".repeat(400));
+        assert_eq!(parse_money(&huge), None);
+        assert_eq!(parse_money(&"9".repeat(400)), None);
+    }
+
+    #[test]
+    fn parser_counts_normalized_values() {
+        let mut parser = MoneyParser::default();
+        parser.parse("100");
+        parser.parse("₱100");
+        assert_eq!(parser.normalized_count(), 1);
+    }
+}
@@ -0,0 +1,244 @@
+use std::collections::HashSet;
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+use crate::dataset::Dataset;
+use crate::metrics;
+use crate::project::Project;
+
+pub const CSV_FILE: &str = "data_profile.csv";
+pub const CHECKS_CSV_FILE: &str = "data_profile_checks.csv";
+pub const JSON_FILE: &str = "data_profile.json";
+
+// Value range of a numeric or date column
+#[derive(Clone, Debug)]
+pub enum Range {
+    Number { min: f64, max: f64, mean: f64 },
+    Date { min: NaiveDate, max: NaiveDate, mean: NaiveDate },
+}
+
+impl Range {
+    fn numbers(values: &[f64]) -> Option<Range> {
+        let min = values.iter().copied().reduce(f64::min)?;
+        let max = values.iter().copied().reduce(f64::max)?;
+        Some(Range::Number { min, max, mean: metrics::mean(values) })
+    }
+
+    fn dates(values: &[NaiveDate]) -> Option<Range> {
+        let min = *values.iter().min()?;
+        let max = *values.iter().max()?;
+        // Mean as days after the earliest date
+        let offsets: Vec<f64> = values.iter().map(|d| (*d - min).num_days() as f64).collect();
+        let mean = min + chrono::Duration::days(metrics::mean(&offsets).round() as i64);
+        Some(Range::Date { min, max, mean })
+    }
+
+    // (min, max, mean) as display strings
+    fn formatted(&self) -> (String, String, String) {
+        match self {
+            Range::Number { min, max, mean } => (format!("{:.2}", min), format!("{:.2}", max), format!("{:.2}", mean)),
+            Range::Date { min, max, mean } => (min.to_string(), max.to_string(), mean.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ColumnProfile {
+    pub column: &'static str,
+    // Blank in the source; always 0 for required columns, whose blank rows
+    // were rejected while loading
+    pub missing: usize,
+    pub distinct: usize,
+    pub range: Option<Range>,
+}
+
+// Rows that loaded but look suspicious
+#[derive(Clone, Debug, Default)]
+pub struct QualityChecks {
+    // Delay for these is clamped to 0 by the reports
+    pub completion_before_start: usize,
+    pub non_positive_budget: usize,
+    pub cost_over_budget: usize,
+}
+
+impl QualityChecks {
+    // (name, count) pairs in a fixed order, as used by the exports
+    pub fn counts(&self) -> [(&'static str, usize); 3] {
+        [
+            ("completion_before_start", self.completion_before_start),
+            ("non_positive_budget", self.non_positive_budget),
+            ("cost_over_budget", self.cost_over_budget),
+        ]
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct DataProfile {
+    pub total_projects: usize,
+    pub columns: Vec<ColumnProfile>,
+    pub checks: QualityChecks,
+}
+
+pub fn profile(dataset: &Dataset) -> DataProfile {
+    let projects = dataset.projects();
+
+    let text = |column: &'static str, get: fn(&Project) -> Option<&str>| {
+        let values: Vec<&str> = projects.iter().filter_map(get).collect();
+        ColumnProfile {
+            column,
+            missing: projects.len() - values.len(),
+            distinct: values.iter().collect::<HashSet<_>>().len(),
+            range: None,
+        }
+    };
+    let number = |column: &'static str, get: fn(&Project) -> Option<f64>| {
+        let values: Vec<f64> = projects.iter().filter_map(get).collect();
+        ColumnProfile {
+            column,
+            missing: projects.len() - values.len(),
+            distinct: values.iter().map(|v| v.to_bits()).collect::<HashSet<_>>().len(),
+            range: Range::numbers(&values),
+        }
+    };
+    let date = |column: &'static str, get: fn(&Project) -> Option<NaiveDate>| {
+        let values: Vec<NaiveDate> = projects.iter().filter_map(get).collect();
+        ColumnProfile {
+            column,
+            missing: projects.len() - values.len(),
+            distinct: values.iter().collect::<HashSet<_>>().len(),
+            range: Range::dates(&values),
+        }
+    };
+
+    let columns = vec![
+        text("MainIsland", |p| Some(&p.main_island)),
+        text("Region", |p| Some(&p.region)),
+        text("Province", |p| p.province.as_deref()),
+        text("LegislativeDistrict", |p| p.legislative_district.as_deref()),
+        text("Municipality", |p| p.municipality.as_deref()),
+        text("DistrictEngineeringOffice", |p| p.district_engineering_office.as_deref()),
+        text("ProjectId", |p| p.project_id.as_deref()),
+        text("ProjectName", |p| p.project_name.as_deref()),
+        text("TypeOfWork", |p| Some(&p.type_of_work)),
+        number("FundingYear", |p| Some(p.funding_year as f64)),
+        number("ApprovedBudgetForContract", |p| Some(p.approved_budget)),
+        number("ContractCost", |p| Some(p.contract_cost)),
+        date("StartDate", |p| Some(p.start_date)),
+        date("ActualCompletionDate", |p| Some(p.actual_completion_date)),
+        text("Contractor", |p| Some(&p.contractor)),
+        number("ContractorCount", |p| p.contractor_count.map(f64::from)),
+        date("PlannedCompletionDate", |p| p.planned_completion_date),
+        number("ContractDurationDays", |p| p.contract_duration_days.map(|d| d as f64)),
+    ];
+
+    let checks = QualityChecks {
+        completion_before_start: projects.iter().filter(|p| metrics::duration_days(p) < 0).count(),
+        non_positive_budget: projects.iter().filter(|p| p.approved_budget <= 0.0).count(),
+        cost_over_budget: projects.iter().filter(|p| p.contract_cost > p.approved_budget).count(),
+    };
+
+    DataProfile {
+        total_projects: projects.len(),
+        columns,
+        checks,
+    }
+}
+
+impl DataProfile {
+    pub fn print(&self) {
+        println!();
+        println!("Data Profile ({} projects)", self.total_projects);
+        println!();
+        println!(
+            "| {:<26} | {:>8} | {:>8} | {:>18} | {:>18} | {:>18} |",
+            "Column", "Missing", "Distinct", "Min", "Max", "Mean"
+        );
+        println!("{}", "-".repeat(113));
+        for c in &self.columns {
+            let (min, max, mean) = c.range.as_ref().map(Range::formatted).unwrap_or_default();
+            println!(
+                "| {:<26} | {:>8} | {:>8} | {:>18} | {:>18} | {:>18} |",
+                c.column, c.missing, c.distinct, min, max, mean
+            );
+        }
+        println!();
+        println!("Checks:");
+        println!("  Completion before start   {:>8}  (delay counted as 0)", self.checks.completion_before_start);
+        println!("  Zero or negative budget   {:>8}", self.checks.non_positive_budget);
+        println!("  Cost greater than budget  {:>8}", self.checks.cost_over_budget);
+    }
+
+    pub fn write_csv(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(["Column", "Missing", "Distinct", "Min", "Max", "Mean"])?;
+        for c in &self.columns {
+            let (min, max, mean) = c.range.as_ref().map(Range::formatted).unwrap_or_default();
+            wtr.write_record(&[
+                c.column.to_string(),
+                c.missing.to_string(),
+                c.distinct.to_string(),
+                min,
+                max,
+                mean,
+            ])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    // The quality checks as their own Check,Count table
+    pub fn write_checks_csv(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let mut wtr = csv::Writer::from_path(path)?;
+        wtr.write_record(["Check", "Count"])?;
+        for (check, count) in self.checks.counts() {
+            wtr.write_record(&[check.to_string(), count.to_string()])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+
+    pub fn to_json(&self) -> Value {
+        let columns: Vec<Value> = self
+            .columns
+            .iter()
+            .map(|c| {
+                let mut column = json!({
+                    "column": c.column,
+                    "missing": c.missing,
+                    "distinct": c.distinct
+                });
+                match &c.range {
+                    Some(Range::Number { min, max, mean }) => {
+                        column["min"] = json!(min);
+                        column["max"] = json!(max);
+                        column["mean"] = json!(mean);
+                    }
+                    Some(Range::Date { min, max, mean }) => {
+                        column["min"] = json!(min.to_string());
+                        column["max"] = json!(max.to_string());
+                        column["mean"] = json!(mean.to_string());
+                    }
+                    None => {}
+                }
+                column
+            })
+            .collect();
+        json!({
+            "total_projects": self.total_projects,
+            "columns": columns,
+            "checks": {
+                "completion_before_start": self.checks.completion_before_start,
+                "non_positive_budget": self.checks.non_positive_budget,
+                "cost_over_budget": self.checks.cost_over_budget
+            }
+        })
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, &self.to_json())?;
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+use std::collections::BTreeSet;
+use chrono::NaiveDate;
+
+// Full DPWH project record. Columns that are not required for the current
+// reports are optional so rows from partial extracts are not rejected.
+#[derive(Clone, Debug)]
+pub struct Project {
+    pub main_island: String,
+    pub region: String,
+    pub province: Option<String>,
+    pub legislative_district: Option<String>,
+    pub municipality: Option<String>,
+    pub district_engineering_office: Option<String>,
+    pub project_id: Option<String>,
+    pub project_name: Option<String>,
+    pub type_of_work: String,
+    pub approved_budget: f64,
+    pub contract_cost: f64,
+    pub start_date: NaiveDate,
+    pub actual_completion_date: NaiveDate,
+    // Canonical name once the project is in a dataset (see contractor_names)
+    pub contractor: String,
+    // Contractor exactly as written in the source file
+    pub contractor_raw: String,
+    pub contractor_count: Option<u32>,
+    // Member firms when the contractor is a joint venture, each resolved to
+    // its canonical name like contractor; empty otherwise
+    pub contractor_members: Vec<String>,
+    // Original target date, or the contract duration it can be derived from;
+    // either one lets delay be measured as slippage instead of duration
+    pub planned_completion_date: Option<NaiveDate>,
+    pub contract_duration_days: Option<i64>,
+    pub funding_year: i32,
+    // Dataset source (loaded file) this project came from
+    pub source_id: usize,
+}
+
+// A valid project for unit tests to adjust with struct-update syntax
+#[cfg(test)]
+pub(crate) fn test_project() -> Project {
+    Project {
+        main_island: "Luzon".to_string(),
+        region: "NCR".to_string(),
+        province: None,
+        legislative_district: None,
+        municipality: None,
+        district_engineering_office: None,
+        project_id: None,
+        project_name: None,
+        type_of_work: "Construction of Flood Mitigation Structure".to_string(),
+        approved_budget: 1_000.0,
+        contract_cost: 900.0,
+        start_date: NaiveDate::from_ymd_opt(2022, 1, 1).unwrap(),
+        actual_completion_date: NaiveDate::from_ymd_opt(2022, 4, 11).unwrap(),
+        contractor: "ALPHA BUILDERS INC".to_string(),
+        contractor_raw: "ALPHA BUILDERS INC".to_string(),
+        contractor_count: None,
+        contractor_members: Vec::new(),
+        planned_completion_date: None,
+        contract_duration_days: None,
+        funding_year: 2022,
+        source_id: 0,
+    }
+}
+
+// Funding years kept when loading, e.g. "2021-2023" or "2018,2020,2024"
+#[derive(Clone, Debug, PartialEq)]
+pub struct FundingYears {
+    years: BTreeSet<i32>,
+}
+
+impl Default for FundingYears {
+    fn default() -> Self {
+        FundingYears { years: (2021..=2023).collect() }
+    }
+}
+
+impl FundingYears {
+    // Every year from start to end inclusive; an empty window is an error
+    // since Report 3 needs a baseline year
+    pub fn range(start: i32, end: i32) -> Result<Self, String> {
+        if start > end {
+            return Err(format!("Invalid funding year range: {}-{}", start, end));
+        }
+        Ok(FundingYears { years: (start..=end).collect() })
+    }
+
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut years = BTreeSet::new();
+        for part in input.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let parse_year = |v: &str| v.trim().parse::<i32>().map_err(|_| format!("Invalid funding year: {:?}", v.trim()));
+            match part.split_once('-') {
+                Some((start, end)) => {
+                    let (start, end) = (parse_year(start)?, parse_year(end)?);
+                    if start > end {
+                        return Err(format!("Invalid funding year range: {}", part));
+                    }
+                    years.extend(start..=end);
+                }
+                None => {
+                    years.insert(parse_year(part)?);
+                }
+            }
+        }
+        if years.is_empty() {
+            return Err("No funding years given".to_string());
+        }
+        Ok(FundingYears { years })
+    }
+
+    pub fn union(&self, other: &FundingYears) -> FundingYears {
+        FundingYears { years: self.years.union(&other.years).copied().collect() }
+    }
+
+    pub fn contains(&self, year: i32) -> bool {
+        self.years.contains(&year)
+    }
+
+    // First year of the window; Report 3 compares against it
+    pub fn baseline(&self) -> i32 {
+        *self.years.iter().next().expect("funding year window is never empty")
+    }
+
+    // Human-readable window with consecutive years collapsed, e.g. "2018–2020, 2024"
+    pub fn label(&self) -> String {
+        let mut spans: Vec<(i32, i32)> = Vec::new();
+        for &y in &self.years {
+            match spans.last_mut() {
+                Some((_, end)) if *end + 1 == y => *end = y,
+                _ => spans.push((y, y)),
+            }
+        }
+        spans
+            .iter()
+            .map(|&(start, end)| if start == end { start.to_string() } else { format!("{}–{}", start, end) })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_ranges_and_lists() {
+        let years = FundingYears::parse("2021-2023").unwrap();
+        assert!(years.contains(2021) && years.contains(2022) && years.contains(2023));
+        assert!(!years.contains(2024));
+
+        let years = FundingYears::parse("2018, 2020,2024").unwrap();
+        assert!(years.contains(2020) && !years.contains(2019));
+        assert_eq!(years.label(), "2018, 2020, 2024");
+    }
+
+    #[test]
+    fn range_rejects_reversed_years() {
+        assert_eq!(FundingYears::range(2021, 2023).unwrap().baseline(), 2021);
+        assert_eq!(FundingYears::range(2022, 2022).unwrap().label(), "2022");
+        assert!(FundingYears::range(2023, 2021).is_err());
+    }
+
+    #[test]
+    fn rejects_bad_windows() {
+        assert!(FundingYears::parse("2023-2021").is_err());
+        assert!(FundingYears::parse("20x1").is_err());
+        assert!(FundingYears::parse(" , ").is_err());
+    }
+}
@@ -0,0 +1,312 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use crate::dataset::Dataset;
+use crate::metrics::{self, DelayBasis};
+use crate::project::Project;
+use super::reliability::{ReliabilityOptions, RiskTier};
+use super::{format_comma_float, truncate_name};
+
+pub const CSV_FILE: &str = "report_2_contractor_ranking.csv";
+
+// Column the ranking is sorted by
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RankingKey {
+    TotalCost,
+    NumProjects,
+    ReliabilityIndex,
+    AvgDelay,
+    TotalSavings,
+}
+
+impl RankingKey {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "cost" | "total-cost" | "totalcost" => Some(RankingKey::TotalCost),
+            "projects" | "num-projects" | "numprojects" => Some(RankingKey::NumProjects),
+            "reliability" | "reliability-index" | "reliabilityindex" => Some(RankingKey::ReliabilityIndex),
+            "delay" | "avg-delay" | "avgdelay" => Some(RankingKey::AvgDelay),
+            "savings" | "total-savings" | "totalsavings" => Some(RankingKey::TotalSavings),
+            _ => None,
+        }
+    }
+
+    // Matches the column header used in the table and CSV
+    pub fn column(&self) -> &'static str {
+        match self {
+            RankingKey::TotalCost => "TotalCost",
+            RankingKey::NumProjects => "NumProjects",
+            RankingKey::ReliabilityIndex => "ReliabilityIndex",
+            RankingKey::AvgDelay => "AvgDelay",
+            RankingKey::TotalSavings => "TotalSavings",
+        }
+    }
+
+    fn value(&self, row: &ContractorRow) -> f64 {
+        match self {
+            RankingKey::TotalCost => row.total_cost,
+            RankingKey::NumProjects => row.num_projects as f64,
+            RankingKey::ReliabilityIndex => row.reliability_index,
+            RankingKey::AvgDelay => row.avg_delay,
+            RankingKey::TotalSavings => row.total_savings,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+impl SortOrder {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "asc" | "ascending" => Some(SortOrder::Ascending),
+            "desc" | "descending" => Some(SortOrder::Descending),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SortOrder::Ascending => "asc",
+            SortOrder::Descending => "desc",
+        }
+    }
+}
+
+// How a joint venture's contract is credited to its member firms
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JvAllocation {
+    // The JV stays one contractor under its combined name
+    Combined,
+    // Each member gets cost and savings divided by the number of members
+    Equal,
+    // Each member is credited with the full cost and savings
+    Full,
+}
+
+impl JvAllocation {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "combined" | "none" => Some(JvAllocation::Combined),
+            "equal" | "split" => Some(JvAllocation::Equal),
+            "full" => Some(JvAllocation::Full),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            JvAllocation::Combined => "combined",
+            JvAllocation::Equal => "equal",
+            JvAllocation::Full => "full",
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RankingOptions {
+    // None keeps every eligible contractor
+    pub top_n: Option<usize>,
+    pub min_projects: usize,
+    pub sort_key: RankingKey,
+    pub order: SortOrder,
+    pub reliability: ReliabilityOptions,
+    pub jv_allocation: JvAllocation,
+}
+
+impl Default for RankingOptions {
+    fn default() -> Self {
+        RankingOptions {
+            top_n: Some(15),
+            min_projects: 5,
+            sort_key: RankingKey::TotalCost,
+            order: SortOrder::Descending,
+            reliability: ReliabilityOptions::default(),
+            jv_allocation: JvAllocation::Combined,
+        }
+    }
+}
+
+impl RankingOptions {
+    // e.g. "Top 15 by TotalCost desc, >=5 Projects, JV allocation: combined"
+    pub fn label(&self) -> String {
+        let limit = match self.top_n {
+            Some(n) => format!("Top {}", n),
+            None => "All contractors".to_string(),
+        };
+        format!(
+            "{} by {} {}, >={} Projects, JV allocation: {}",
+            limit,
+            self.sort_key.column(),
+            self.order.label(),
+            self.min_projects,
+            self.jv_allocation.label()
+        )
+    }
+}
+
+// Report 2 row: one contractor with at least the minimum number of projects
+#[derive(Clone, Debug)]
+pub struct ContractorRow {
+    pub contractor: String,
+    pub total_cost: f64,
+    pub num_projects: usize,
+    pub avg_delay: f64,
+    pub delay_basis: DelayBasis,
+    pub total_savings: f64,
+    pub reliability_index: f64,
+    pub risk_flag: RiskTier,
+}
+
+// Report 2: Top Contractors Performance Ranking, sorted and cut off as the
+// options say. Ties keep a stable order by contractor name. When JVs are
+// split, a project counts once toward each member's NumProjects and delay.
+pub fn contractor_ranking(dataset: &Dataset, options: &RankingOptions) -> Vec<ContractorRow> {
+    // Group by Contractor, with the share of each project credited to it
+    let mut contractor_group: HashMap<String, Vec<(&Project, f64)>> = HashMap::new();
+    for p in dataset.projects() {
+        let members = match options.jv_allocation {
+            JvAllocation::Combined => vec![p.contractor.clone()],
+            JvAllocation::Equal | JvAllocation::Full if !p.contractor_members.is_empty() => p.contractor_members.clone(),
+            JvAllocation::Equal | JvAllocation::Full => vec![p.contractor.clone()],
+        };
+        let share = match options.jv_allocation {
+            JvAllocation::Equal => 1.0 / members.len() as f64,
+            JvAllocation::Combined | JvAllocation::Full => 1.0,
+        };
+        for member in members {
+            contractor_group.entry(member).or_default().push((p, share));
+        }
+    }
+
+    let mut contractor_rows: Vec<ContractorRow> = Vec::new();
+
+    for (contractor, items) in contractor_group {
+        if items.len() < options.min_projects {
+            continue;
+        }
+
+        let total_cost: f64 = items.iter().map(|(p, share)| p.contract_cost * share).sum();
+        let total_savings: f64 = items.iter().map(|(p, share)| metrics::savings(p) * share).sum();
+
+        let delays: Vec<f64> = items.iter().map(|(p, _)| metrics::delay_days(p) as f64).collect();
+        let avg_delay = metrics::mean(&delays);
+
+        // Index and flag are filled in below, once every contractor is known
+        contractor_rows.push(ContractorRow {
+            contractor,
+            total_cost,
+            num_projects: items.len(),
+            avg_delay,
+            delay_basis: DelayBasis::of(items.iter().map(|(p, _)| *p)),
+            total_savings,
+            reliability_index: 0.0,
+            risk_flag: RiskTier::Critical,
+        });
+    }
+
+    let reliability = &options.reliability;
+    let inputs: Vec<(f64, f64)> = contractor_rows
+        .iter()
+        .map(|r| (r.avg_delay, metrics::savings_ratio(r.total_savings, r.total_cost)))
+        .collect();
+    for (row, index) in contractor_rows.iter_mut().zip(reliability.score(&inputs)) {
+        row.reliability_index = index;
+        row.risk_flag = reliability.tiers.classify(index);
+    }
+
+    contractor_rows.sort_by(|a, b| {
+        let by_key = options.sort_key.value(a).total_cmp(&options.sort_key.value(b));
+        let by_key = match options.order {
+            SortOrder::Ascending => by_key,
+            SortOrder::Descending => by_key.reverse(),
+        };
+        match by_key {
+            Ordering::Equal => a.contractor.cmp(&b.contractor),
+            other => other,
+        }
+    });
+
+    if let Some(n) = options.top_n {
+        contractor_rows.truncate(n);
+    }
+    contractor_rows
+}
+
+pub fn print(rows: &[ContractorRow], options: &RankingOptions) {
+    println!();
+    println!("Report 2: Top Contractors Performance Ranking");
+    println!("({})", options.label());
+    println!("({})", options.reliability.label());
+    println!("({})", metrics::DELAY_NOTE);
+    println!();
+
+    // Print formatted table
+    println!(
+        "| {:<4} | {:<45} | {:<18} | {:<12} | {:<10} | {:<10} | {:<16} | {:<18} | {:<13} |",
+        "Rank", "Contractor", "TotalCost", "NumProjects", "AvgDelay", "DelayBasis", "TotalSavings", "ReliabilityIndex",
+        "RiskFlag"
+    );
+    println!("{}", "-".repeat(181));
+
+    for (i, r) in rows.iter().enumerate() {
+        println!(
+            "| {:<4} | {:<45} | {:>18} | {:>12} | {:>10.1} | {:<10} | {:>16} | {:>18.2} | {:<13} |",
+            i + 1,
+            truncate_name(&r.contractor, 45),
+            format_comma_float(r.total_cost),
+            r.num_projects,
+            r.avg_delay,
+            r.delay_basis.label(),
+            format_comma_float(r.total_savings),
+            r.reliability_index,
+            r.risk_flag
+        );
+    }
+}
+
+pub fn write_csv(rows: &[ContractorRow], options: &RankingOptions, path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record([
+        "Rank",
+        "Contractor",
+        "TotalCost",
+        "NumProjects",
+        "AvgDelay",
+        "DelayBasis",
+        "TotalSavings",
+        "ReliabilityIndex",
+        "RiskFlag",
+        "RankedBy",
+        "MinProjects",
+        "Limit",
+        "ReliabilityMode",
+        "JvAllocation",
+    ])?;
+
+    for (i, r) in rows.iter().enumerate() {
+        wtr.write_record(&[
+            (i + 1).to_string(),
+            r.contractor.clone(),
+            format!("{:.2}", r.total_cost),
+            r.num_projects.to_string(),
+            format!("{:.2}", r.avg_delay),
+            r.delay_basis.label().to_string(),
+            format!("{:.2}", r.total_savings),
+            format!("{:.2}", r.reliability_index),
+            r.risk_flag.to_string(),
+            format!("{} {}", options.sort_key.column(), options.order.label()),
+            options.min_projects.to_string(),
+            // "all" distinguishes an uncapped export that happens to be short
+            options.top_n.map_or("all".to_string(), |n| n.to_string()),
+            options.reliability.mode.label().to_string(),
+            options.jv_allocation.label().to_string(),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use std::error::Error;
+use std::path::Path;
+use num_format::{Locale, ToFormattedString};
+use crate::dataset::Dataset;
+
+pub mod contractors;
+pub mod regional;
+pub mod reliability;
+pub mod rollup;
+pub mod summary;
+pub mod trends;
+
+pub use contractors::{contractor_ranking, ContractorRow, JvAllocation, RankingKey, RankingOptions, SortOrder};
+pub use regional::{regional_summary, DelayPercentiles, Dimension, Normalization, RegionalOptions, RegionalRow};
+pub use reliability::{ReliabilityMode, ReliabilityOptions, RiskTier, RiskTiers};
+pub use rollup::{hierarchical_rollup, RollupRow};
+pub use summary::{summary, Coverage, Summary};
+pub use trends::{annual_trends, TrendRow, WorstOverrun};
+
+// The outputs generate_reports can produce
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportKind {
+    Regional,
+    Contractors,
+    Trends,
+    Rollup,
+    Summary,
+}
+
+impl ReportKind {
+    pub const ALL: [ReportKind; 5] = [
+        ReportKind::Regional,
+        ReportKind::Contractors,
+        ReportKind::Trends,
+        ReportKind::Rollup,
+        ReportKind::Summary,
+    ];
+
+    // Accepts "1" to "4" or "summary"
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "1" => Some(ReportKind::Regional),
+            "2" => Some(ReportKind::Contractors),
+            "3" => Some(ReportKind::Trends),
+            "4" | "rollup" => Some(ReportKind::Rollup),
+            "s" | "summary" => Some(ReportKind::Summary),
+            _ => None,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            ReportKind::Regional => "Report 1: Regional Flood Mitigation Efficiency Summary",
+            ReportKind::Contractors => "Report 2: Top Contractors Performance Ranking",
+            ReportKind::Trends => "Report 3: Annual Project Type Cost Overrun Trends",
+            ReportKind::Rollup => "Report 4: Hierarchical Roll-up with Subtotals",
+            ReportKind::Summary => "Summary Stats (summary.json)",
+        }
+    }
+}
+
+// Parameters for the individual reports
+#[derive(Clone, Debug, Default)]
+pub struct ReportOptions {
+    pub regional: RegionalOptions,
+    pub ranking: RankingOptions,
+}
+
+// Computes only the requested reports, prints them and overwrites their
+// CSV/JSON exports in out_dir. Other exports are left untouched.
+pub fn generate_reports(
+    dataset: &Dataset,
+    out_dir: &Path,
+    kinds: &[ReportKind],
+    options: &ReportOptions,
+) -> Result<(), Box<dyn Error>> {
+    println!("Generating reports...");
+    let funding_years = dataset.funding_years();
+
+    for kind in kinds {
+        match kind {
+            ReportKind::Regional => {
+                let rows = regional_summary(dataset, &options.regional);
+                regional::print(&rows, &options.regional, &funding_years);
+                let path = out_dir.join(regional::CSV_FILE);
+                regional::write_csv(&rows, &options.regional, &path)?;
+                println!();
+                println!("Full table exported to {}", path.display());
+            }
+            ReportKind::Contractors => {
+                let rows = contractor_ranking(dataset, &options.ranking);
+                contractors::print(&rows, &options.ranking);
+                let path = out_dir.join(contractors::CSV_FILE);
+                contractors::write_csv(&rows, &options.ranking, &path)?;
+                println!();
+                println!("Full table exported to {}", path.display());
+            }
+            ReportKind::Trends => {
+                let rows = annual_trends(dataset);
+                trends::print(&rows, funding_years.baseline());
+                let path = out_dir.join(trends::CSV_FILE);
+                trends::write_csv(&rows, &path)?;
+                println!();
+                println!("(Full table exported to {})", path.display());
+            }
+            ReportKind::Rollup => {
+                let rows = hierarchical_rollup(dataset);
+                rollup::print(&rows, &funding_years);
+                let path = out_dir.join(rollup::CSV_FILE);
+                rollup::write_csv(&rows, &path)?;
+                println!();
+                println!("Full table exported to {}", path.display());
+            }
+            ReportKind::Summary => {
+                let summary = summary(dataset);
+                summary.write_json(&out_dir.join(summary::JSON_FILE))?;
+                println!();
+                println!("Summary Stats ({}):", summary::JSON_FILE);
+                println!("{}", summary.to_json());
+            }
+        }
+    }
+    Ok(())
+}
+
+// Handles negatives and formats with commas + 2 decimal places
+pub(crate) fn format_comma_float(val: f64) -> String {
+    let sign = if val.is_sign_negative() { "-" } else { "" };
+    // Round to cents first so .995 carries into the whole part
+    let cents = (val.abs() * 100.0).round() as i64;
+    let whole = cents / 100;
+    let fraction = cents % 100;
+    format!("{}{}.{:02}", sign, whole.to_formatted_string(&Locale::en), fraction)
+}
+
+// Truncate long names for display. Counts chars, not bytes, so names such
+// as "Las Piñas" are never cut inside a character.
+pub(crate) fn truncate_name(name: &str, max_len: usize) -> String {
+    if name.chars().count() > max_len {
+        let kept: String = name.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    } else {
+        name.to_string()
+    }
+}
@@ -0,0 +1,457 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use crate::dataset::Dataset;
+use crate::metrics::{self, percentile, percentile_rank, DelayBasis};
+use crate::project::{FundingYears, Project};
+use super::{format_comma_float, truncate_name};
+
+pub const CSV_FILE: &str = "report_1_regional_summary.csv";
+
+// How raw efficiency (median savings per delay day) is mapped onto 0–100.
+// Every method gives 50 to all groups when the raw scores are all equal.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Normalization {
+    // Linear between the lowest (0) and highest (100) group
+    MinMax,
+    // Mid-rank percentile among the groups
+    RankPercentile,
+    // 50 at the mean, 0 and 100 at three standard deviations either side
+    ZScore,
+    // Min-max after clamping raw scores to their 5th and 95th percentiles,
+    // so a single outlier cannot compress everyone else
+    Winsorized,
+}
+
+impl Normalization {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "minmax" | "min-max" => Some(Normalization::MinMax),
+            "rank" | "percentile" | "rank-percentile" => Some(Normalization::RankPercentile),
+            "z" | "zscore" | "z-score" => Some(Normalization::ZScore),
+            "winsor" | "winsorized" => Some(Normalization::Winsorized),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            Normalization::MinMax => "min-max",
+            Normalization::RankPercentile => "rank-percentile",
+            Normalization::ZScore => "z-score",
+            Normalization::Winsorized => "winsorized",
+        }
+    }
+
+    pub fn apply(&self, raw: &[f64]) -> Vec<f64> {
+        let (Some(min), Some(max)) = (raw.iter().copied().reduce(f64::min), raw.iter().copied().reduce(f64::max)) else {
+            return Vec::new();
+        };
+        if max <= min {
+            return vec![50.0; raw.len()];
+        }
+        match self {
+            Normalization::MinMax => raw.iter().map(|v| (v - min) / (max - min) * 100.0).collect(),
+            Normalization::RankPercentile => raw.iter().map(|v| percentile_rank(raw, *v) * 100.0).collect(),
+            Normalization::ZScore => {
+                let mean = metrics::mean(raw);
+                let variance: Vec<f64> = raw.iter().map(|v| (v - mean).powi(2)).collect();
+                let std_dev = metrics::mean(&variance).sqrt();
+                raw.iter()
+                    .map(|v| (50.0 + (v - mean) / std_dev * 50.0 / 3.0).clamp(0.0, 100.0))
+                    .collect()
+            }
+            Normalization::Winsorized => {
+                let low = percentile(raw, 0.05);
+                let high = percentile(raw, 0.95);
+                if high <= low {
+                    return vec![50.0; raw.len()];
+                }
+                raw.iter().map(|v| (v.clamp(low, high) - low) / (high - low) * 100.0).collect()
+            }
+        }
+    }
+}
+
+// Project attribute Report 1 can group by
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Dimension {
+    MainIsland,
+    Region,
+    Province,
+    LegislativeDistrict,
+    Municipality,
+    DistrictEngineeringOffice,
+    TypeOfWork,
+}
+
+impl Dimension {
+    // Accepts the CSV column name in any case, or a short alias
+    pub fn parse(input: &str) -> Option<Self> {
+        let key: String = input
+            .trim()
+            .to_ascii_lowercase()
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect();
+        match key.as_str() {
+            "mainisland" | "island" => Some(Dimension::MainIsland),
+            "region" => Some(Dimension::Region),
+            "province" => Some(Dimension::Province),
+            "legislativedistrict" | "district" => Some(Dimension::LegislativeDistrict),
+            "municipality" => Some(Dimension::Municipality),
+            "districtengineeringoffice" | "deo" => Some(Dimension::DistrictEngineeringOffice),
+            "typeofwork" | "type" => Some(Dimension::TypeOfWork),
+            _ => None,
+        }
+    }
+
+    // "province,municipality"; order is kept and repeats are dropped
+    pub fn parse_list(input: &str) -> Result<Vec<Self>, String> {
+        let mut dimensions = Vec::new();
+        for name in input.split(',') {
+            let dimension = Dimension::parse(name).ok_or_else(|| format!("Unknown grouping column: {:?}", name.trim()))?;
+            if !dimensions.contains(&dimension) {
+                dimensions.push(dimension);
+            }
+        }
+        Ok(dimensions)
+    }
+
+    // Matches the CSV column name
+    pub fn column(&self) -> &'static str {
+        match self {
+            Dimension::MainIsland => "MainIsland",
+            Dimension::Region => "Region",
+            Dimension::Province => "Province",
+            Dimension::LegislativeDistrict => "LegislativeDistrict",
+            Dimension::Municipality => "Municipality",
+            Dimension::DistrictEngineeringOffice => "DistrictEngineeringOffice",
+            Dimension::TypeOfWork => "TypeOfWork",
+        }
+    }
+
+    // Optional columns left blank in the source are grouped as "(blank)"
+    pub fn value(&self, project: &Project) -> String {
+        let optional = |v: &Option<String>| v.clone().unwrap_or_else(|| "(blank)".to_string());
+        match self {
+            Dimension::MainIsland => project.main_island.clone(),
+            Dimension::Region => project.region.clone(),
+            Dimension::Province => optional(&project.province),
+            Dimension::LegislativeDistrict => optional(&project.legislative_district),
+            Dimension::Municipality => optional(&project.municipality),
+            Dimension::DistrictEngineeringOffice => optional(&project.district_engineering_office),
+            Dimension::TypeOfWork => project.type_of_work.clone(),
+        }
+    }
+
+    // Table column width
+    fn width(&self) -> usize {
+        match self {
+            Dimension::MainIsland => 10,
+            Dimension::Province | Dimension::LegislativeDistrict => 25,
+            Dimension::Municipality => 30,
+            Dimension::Region | Dimension::DistrictEngineeringOffice | Dimension::TypeOfWork => 40,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionalOptions {
+    // Columns rows are grouped by, outermost first
+    pub group_by: Vec<Dimension>,
+    // Delay thresholds in days, ascending; each gets a "% of projects over" column
+    pub delay_bands: Vec<i64>,
+    pub normalization: Normalization,
+}
+
+impl Default for RegionalOptions {
+    fn default() -> Self {
+        RegionalOptions {
+            group_by: vec![Dimension::Region, Dimension::MainIsland],
+            delay_bands: vec![30, 90, 180, 365],
+            normalization: Normalization::MinMax,
+        }
+    }
+}
+
+impl RegionalOptions {
+    // "30,90,180,365"; sorted and de-duplicated
+    pub fn parse_bands(input: &str) -> Result<Vec<i64>, String> {
+        let mut bands = input
+            .split(',')
+            .map(|b| match b.trim().parse::<i64>() {
+                Ok(days) if days >= 0 => Ok(days),
+                _ => Err(format!("Invalid delay band: {:?}", b.trim())),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        bands.sort_unstable();
+        bands.dedup();
+        Ok(bands)
+    }
+}
+
+// Completion delay distribution of one group, in days
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DelayPercentiles {
+    pub p25: f64,
+    pub p50: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub max: f64,
+}
+
+// Report 1 row: one group, by default (Region, MainIsland)
+#[derive(Clone, Debug)]
+pub struct RegionalRow {
+    // One value per RegionalOptions::group_by dimension, in the same order
+    pub group: Vec<String>,
+    pub total_budget: f64,
+    pub median_savings: f64,
+    pub avg_delay: f64,
+    pub delay_basis: DelayBasis,
+    pub delay_percentiles: DelayPercentiles,
+    // (threshold days, % of projects delayed longer), one per configured band
+    pub delay_bands: Vec<(i64, f64)>,
+    pub efficiency_score: f64,
+}
+
+// Report 1: Regional Flood Mitigation Efficiency Summary, sorted by
+// descending EfficiencyScore
+pub fn regional_summary(dataset: &Dataset, options: &RegionalOptions) -> Vec<RegionalRow> {
+    let mut grouped: HashMap<Vec<String>, Vec<&Project>> = HashMap::new();
+    for p in dataset.projects() {
+        let key = options.group_by.iter().map(|d| d.value(p)).collect();
+        grouped.entry(key).or_default().push(p);
+    }
+
+    let mut rows: Vec<RegionalRow> = Vec::new();
+
+    for (group, items) in grouped {
+        let total_budget: f64 = items.iter().map(|p| p.approved_budget).sum();
+
+        let savings: Vec<f64> = items.iter().map(|p| metrics::savings(p)).collect();
+        let median_savings = metrics::median(&savings);
+
+        let delays: Vec<f64> = items.iter().map(|p| metrics::delay_days(p) as f64).collect();
+        let avg_delay = metrics::mean(&delays);
+        let delay_percentiles = DelayPercentiles {
+            p25: percentile(&delays, 0.25),
+            p50: percentile(&delays, 0.50),
+            p75: percentile(&delays, 0.75),
+            p90: percentile(&delays, 0.90),
+            max: percentile(&delays, 1.0),
+        };
+        let delay_bands = options
+            .delay_bands
+            .iter()
+            .map(|&threshold| (threshold, metrics::share_pct(&delays, |d| *d > threshold as f64)))
+            .collect();
+
+        // Compute efficiency score = (median_savings / avg_delay) * 100. With
+        // slippage a group can average under a day late; it is divided by one
+        // day so finishing on time scores best for its savings instead of 0.
+        let raw_efficiency = (median_savings / avg_delay.max(1.0)) * 100.0;
+
+        rows.push(RegionalRow {
+            group,
+            total_budget,
+            median_savings,
+            avg_delay,
+            delay_basis: DelayBasis::of(items.iter().copied()),
+            delay_percentiles,
+            delay_bands,
+            efficiency_score: raw_efficiency,
+        });
+    }
+
+    // Normalize efficiency scores to 0–100 range
+    let raw: Vec<f64> = rows.iter().map(|r| r.efficiency_score).collect();
+    for (r, score) in rows.iter_mut().zip(options.normalization.apply(&raw)) {
+        r.efficiency_score = score;
+    }
+
+    // Sort descending by EfficiencyScore, ties by group
+    rows.sort_by(|a, b| {
+        b.efficiency_score
+            .partial_cmp(&a.efficiency_score)
+            .unwrap()
+            .then_with(|| a.group.cmp(&b.group))
+    });
+    rows
+}
+
+pub fn print(rows: &[RegionalRow], options: &RegionalOptions, funding_years: &FundingYears) {
+    println!();
+    println!("Report 1: Regional Flood Mitigation Efficiency Summary");
+    println!(
+        "(Aggregated by {}; {} Projects; EfficiencyScore normalization: {})",
+        options.group_by.iter().map(|d| d.column()).collect::<Vec<_>>().join(" & "),
+        funding_years.label(),
+        options.normalization.label()
+    );
+    println!("({})", metrics::DELAY_NOTE);
+    println!();
+
+    // Header with fixed widths; one column per delay band
+    let mut header = String::from("|");
+    for d in &options.group_by {
+        header.push_str(&format!(" {:<width$} |", d.column(), width = d.width()));
+    }
+    header.push_str(&format!(
+        " {:>18} | {:>15} | {:>13} | {:<10} | {:>8} | {:>8} | {:>8} | {:>8} | {:>8} |",
+        "TotalBudget", "MedianSavings", "AvgDelayDays", "DelayBasis", "P25", "P50", "P75", "P90", "Max"
+    ));
+    for threshold in &options.delay_bands {
+        header.push_str(&format!(" {:>12} |", format!("Delay>{}Pct", threshold)));
+    }
+    header.push_str(&format!(" {:>17} |", "EfficiencyScore"));
+    println!("{}", header);
+    println!("{}", "-".repeat(header.len()));
+
+    // Single loop: print each row once
+    for r in rows {
+        let d = &r.delay_percentiles;
+        let mut line = String::from("|");
+        for (d, value) in options.group_by.iter().zip(&r.group) {
+            let width = d.width();
+            line.push_str(&format!(" {:<width$} |", truncate_name(value.trim(), width)));
+        }
+        line.push_str(&format!(
+            " {:>18} | {:>15} | {:>13.2} | {:<10} | {:>8.1} | {:>8.1} | {:>8.1} | {:>8.1} | {:>8.0} |",
+            format_comma_float(r.total_budget),
+            format_comma_float(r.median_savings),
+            r.avg_delay,
+            r.delay_basis.label(),
+            d.p25,
+            d.p50,
+            d.p75,
+            d.p90,
+            d.max
+        ));
+        for (_, pct) in &r.delay_bands {
+            line.push_str(&format!(" {:>12.1} |", pct));
+        }
+        line.push_str(&format!(" {:>17.2} |", r.efficiency_score));
+        println!("{}", line);
+    }
+}
+
+// Export CSV (sorted)
+pub fn write_csv(rows: &[RegionalRow], options: &RegionalOptions, path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    let mut header: Vec<String> = options.group_by.iter().map(|d| d.column().to_string()).collect();
+    header.extend([
+        "TotalBudget",
+        "MedianSavings",
+        "AvgDelayDays",
+        "DelayBasis",
+        "DelayP25",
+        "DelayP50",
+        "DelayP75",
+        "DelayP90",
+        "DelayMax",
+    ]
+    .iter()
+    .map(|h| h.to_string()));
+    header.extend(options.delay_bands.iter().map(|t| format!("DelayOver{}Pct", t)));
+    header.push("EfficiencyScore".to_string());
+    header.push("Normalization".to_string());
+    wtr.write_record(&header)?;
+    for r in rows {
+        let d = &r.delay_percentiles;
+        let mut record = r.group.clone();
+        record.extend([
+            format!("{:.2}", r.total_budget),
+            format!("{:.2}", r.median_savings),
+            format!("{:.2}", r.avg_delay),
+            r.delay_basis.label().to_string(),
+            format!("{:.1}", d.p25),
+            format!("{:.1}", d.p50),
+            format!("{:.1}", d.p75),
+            format!("{:.1}", d.p90),
+            format!("{:.0}", d.max),
+        ]);
+        record.extend(r.delay_bands.iter().map(|(_, pct)| format!("{:.1}", pct)));
+        record.push(format!("{:.2}", r.efficiency_score));
+        record.push(options.normalization.label().to_string());
+        wtr.write_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use crate::dataset::DuplicatePolicy;
+    use crate::project::{test_project, FundingYears};
+
+    const METHODS: [Normalization; 4] = [
+        Normalization::MinMax,
+        Normalization::RankPercentile,
+        Normalization::ZScore,
+        Normalization::Winsorized,
+    ];
+
+    #[test]
+    fn equal_scores_are_all_50() {
+        for method in METHODS {
+            assert_eq!(method.apply(&[7.0, 7.0, 7.0]), [50.0, 50.0, 50.0], "{}", method.label());
+            assert!(method.apply(&[]).is_empty());
+        }
+    }
+
+    #[test]
+    fn min_max_and_rank_percentile() {
+        assert_eq!(Normalization::MinMax.apply(&[10.0, 20.0, 30.0]), [0.0, 50.0, 100.0]);
+        assert_eq!(Normalization::RankPercentile.apply(&[10.0, 20.0, 20.0, 1000.0]), [0.0, 50.0, 50.0, 100.0]);
+    }
+
+    #[test]
+    fn z_score_is_centered_and_clamped() {
+        let mut raw = vec![0.0; 19];
+        raw.push(100.0);
+        let scores = Normalization::ZScore.apply(&raw);
+        assert_eq!(scores[19], 100.0);
+        assert!(scores[0] > 40.0 && scores[0] < 50.0);
+        assert_eq!(Normalization::ZScore.apply(&[-1.0, 0.0, 1.0])[1], 50.0);
+    }
+
+    #[test]
+    fn winsorized_limits_an_outlier() {
+        let mut raw: Vec<f64> = (1..=19).map(f64::from).collect();
+        raw.push(1000.0);
+        let winsorized = Normalization::Winsorized.apply(&raw);
+        let min_max = Normalization::MinMax.apply(&raw);
+        assert_eq!(winsorized[0], 0.0);
+        assert_eq!(winsorized[19], 100.0);
+        // The middle of the pack keeps its spread instead of sitting near 0
+        assert!(min_max[9] < 1.0);
+        assert!(winsorized[9] > 10.0);
+    }
+
+    #[test]
+    fn on_time_group_scores_best() {
+        let planned = NaiveDate::from_ymd_opt(2022, 4, 11);
+        let on_time = Project {
+            region: "NCR".to_string(),
+            planned_completion_date: planned,
+            ..test_project()
+        };
+        let late = Project {
+            region: "Region I".to_string(),
+            planned_completion_date: planned.map(|d| d - chrono::Duration::days(30)),
+            ..test_project()
+        };
+        let mut dataset = Dataset::default();
+        dataset.add_source("a", FundingYears::default(), vec![on_time, late], DuplicatePolicy::KeepFirst);
+
+        let rows = regional_summary(&dataset, &RegionalOptions::default());
+        assert_eq!(rows[0].group[0], "NCR");
+        assert_eq!(rows[0].avg_delay, 0.0);
+        assert_eq!(rows[0].efficiency_score, 100.0);
+        assert_eq!(rows[1].efficiency_score, 0.0);
+    }
+}
@@ -0,0 +1,252 @@
+use std::fmt;
+use crate::metrics::{percentile_rank, weighted_mean};
+
+// How Report 2 turns a contractor's delay and savings into a 0–100 index
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ReliabilityMode {
+    // Weighted mean of a delay score, 1 - avg_delay / horizon, and a savings
+    // score, savings_ratio / savings_target, each clamped to 0–1 and scaled
+    // to 0–100. Lateness and overruns can only lower the index.
+    Formula,
+    // Weighted mean of the contractor's percentile rank for low delay and for
+    // high savings ratio among the ranked contractors, scaled to 0–100
+    Percentile,
+}
+
+impl ReliabilityMode {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "formula" => Some(ReliabilityMode::Formula),
+            "percentile" => Some(ReliabilityMode::Percentile),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReliabilityMode::Formula => "formula",
+            ReliabilityMode::Percentile => "percentile",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskTier {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+impl fmt::Display for RiskTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            RiskTier::Low => "Low Risk",
+            RiskTier::Medium => "Medium Risk",
+            RiskTier::High => "High Risk",
+            RiskTier::Critical => "Critical Risk",
+        };
+        // pad() so table column widths apply
+        f.pad(label)
+    }
+}
+
+// Lowest reliability index that still earns each tier; anything below
+// `high` is Critical
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RiskTiers {
+    pub low: f64,
+    pub medium: f64,
+    pub high: f64,
+}
+
+impl Default for RiskTiers {
+    fn default() -> Self {
+        RiskTiers {
+            low: 75.0,
+            medium: 50.0,
+            high: 25.0,
+        }
+    }
+}
+
+impl RiskTiers {
+    // "75,50,25" = Low, Medium and High cut-offs, highest first
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let values = input
+            .split(',')
+            .map(|v| v.trim().parse::<f64>().map_err(|_| format!("Invalid risk cut-off: {:?}", v.trim())))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [low, medium, high] = values[..] else {
+            return Err(format!("Expected three risk cut-offs (Low,Medium,High), got {:?}", input));
+        };
+        if !(low >= medium && medium >= high) {
+            return Err(format!("Risk cut-offs must be in descending order, got {:?}", input));
+        }
+        Ok(RiskTiers { low, medium, high })
+    }
+
+    pub fn classify(&self, reliability_index: f64) -> RiskTier {
+        if reliability_index >= self.low {
+            RiskTier::Low
+        } else if reliability_index >= self.medium {
+            RiskTier::Medium
+        } else if reliability_index >= self.high {
+            RiskTier::High
+        } else {
+            RiskTier::Critical
+        }
+    }
+
+    pub fn label(&self) -> String {
+        format!("Low>={} Medium>={} High>={}", self.low, self.medium, self.high)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReliabilityOptions {
+    pub mode: ReliabilityMode,
+    // Formula mode: average delay at which the delay score reaches zero
+    pub delay_horizon_days: f64,
+    // Formula mode: savings ratio that earns the full savings score
+    pub savings_target: f64,
+    pub delay_weight: f64,
+    pub savings_weight: f64,
+    pub tiers: RiskTiers,
+}
+
+impl Default for ReliabilityOptions {
+    fn default() -> Self {
+        ReliabilityOptions {
+            mode: ReliabilityMode::Formula,
+            // Delay falls back to total duration when no planned completion is
+            // given, and typical durations run 100–400 days; a 90-day horizon
+            // scored nearly every contractor 0
+            delay_horizon_days: 730.0,
+            savings_target: 0.10,
+            delay_weight: 1.0,
+            savings_weight: 1.0,
+            tiers: RiskTiers::default(),
+        }
+    }
+}
+
+impl ReliabilityOptions {
+    pub fn label(&self) -> String {
+        let scoring = match self.mode {
+            ReliabilityMode::Formula => format!(
+                "formula, horizon {}d, savings target {}, delay weight {}, savings weight {}",
+                self.delay_horizon_days, self.savings_target, self.delay_weight, self.savings_weight
+            ),
+            ReliabilityMode::Percentile => format!(
+                "percentile, delay weight {}, savings weight {}",
+                self.delay_weight, self.savings_weight
+            ),
+        };
+        format!("Reliability: {}; tiers {}", scoring, self.tiers.label())
+    }
+
+    // With both weights at 0 every contractor would score 0 (Critical Risk)
+    pub fn check_weights(&self) -> Result<(), String> {
+        if self.delay_weight == 0.0 && self.savings_weight == 0.0 {
+            return Err("Delay weight and savings weight cannot both be 0".to_string());
+        }
+        Ok(())
+    }
+
+    // Scores every contractor at once since percentile mode is relative.
+    // Inputs are (avg_delay, savings_ratio) pairs; output is in the same order.
+    pub fn score(&self, contractors: &[(f64, f64)]) -> Vec<f64> {
+        match self.mode {
+            ReliabilityMode::Formula => contractors
+                .iter()
+                .map(|&(avg_delay, savings_ratio)| {
+                    let delay_score = (1.0 - avg_delay / self.delay_horizon_days).clamp(0.0, 1.0);
+                    let savings_score = (savings_ratio / self.savings_target).clamp(0.0, 1.0);
+                    weighted_mean(&[(delay_score, self.delay_weight), (savings_score, self.savings_weight)]) * 100.0
+                })
+                .collect(),
+            ReliabilityMode::Percentile => {
+                let delays: Vec<f64> = contractors.iter().map(|c| c.0).collect();
+                let ratios: Vec<f64> = contractors.iter().map(|c| c.1).collect();
+                contractors
+                    .iter()
+                    .map(|&(avg_delay, savings_ratio)| {
+                        // Shorter delay is better, larger savings ratio is better
+                        let delay_score = 1.0 - percentile_rank(&delays, avg_delay);
+                        let savings_score = percentile_rank(&ratios, savings_ratio);
+                        weighted_mean(&[(delay_score, self.delay_weight), (savings_score, self.savings_weight)]) * 100.0
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formula_rewards_savings_and_penalizes_delay() {
+        let options = ReliabilityOptions::default();
+        // (avg_delay, savings_ratio)
+        let scores = options.score(&[(0.0, 0.10), (365.0, 0.05), (364.0, -0.01), (364.0, 0.001), (2000.0, -0.5)]);
+        assert_eq!(scores[0], 100.0);
+        assert_eq!(scores[1], 50.0);
+        // Lateness with an overrun never outscores lateness with savings
+        assert!(scores[2] < scores[3]);
+        assert_eq!(scores[4], 0.0);
+    }
+
+    #[test]
+    fn formula_weights() {
+        let options = ReliabilityOptions {
+            delay_weight: 3.0,
+            savings_weight: 1.0,
+            ..ReliabilityOptions::default()
+        };
+        // Delay score 1, savings score 0
+        assert_eq!(options.score(&[(0.0, 0.0)]), [75.0]);
+    }
+
+    #[test]
+    fn percentile_mode_ranks_with_ties() {
+        let options = ReliabilityOptions {
+            mode: ReliabilityMode::Percentile,
+            ..ReliabilityOptions::default()
+        };
+        let scores = options.score(&[(10.0, 0.2), (20.0, 0.1), (20.0, 0.1), (30.0, 0.0)]);
+        assert_eq!(scores, [100.0, 50.0, 50.0, 0.0]);
+        assert_eq!(options.score(&[(10.0, 0.2)]), [50.0]);
+    }
+
+    #[test]
+    fn tiers_classify_at_each_cut_off() {
+        let tiers = RiskTiers::default();
+        assert_eq!(tiers.classify(100.0), RiskTier::Low);
+        assert_eq!(tiers.classify(75.0), RiskTier::Low);
+        assert_eq!(tiers.classify(74.99), RiskTier::Medium);
+        assert_eq!(tiers.classify(50.0), RiskTier::Medium);
+        assert_eq!(tiers.classify(49.99), RiskTier::High);
+        assert_eq!(tiers.classify(25.0), RiskTier::High);
+        assert_eq!(tiers.classify(24.99), RiskTier::Critical);
+        assert_eq!(tiers.classify(0.0), RiskTier::Critical);
+    }
+
+    #[test]
+    fn tier_parsing() {
+        assert_eq!(RiskTiers::parse("80, 60, 40").unwrap().medium, 60.0);
+        assert!(RiskTiers::parse("40,60,80").is_err());
+        assert!(RiskTiers::parse("75,50").is_err());
+        assert!(RiskTiers::parse("75,x,25").is_err());
+    }
+
+    #[test]
+    fn rejects_all_zero_weights() {
+        let mut options = ReliabilityOptions { savings_weight: 0.0, ..ReliabilityOptions::default() };
+        assert!(options.check_weights().is_ok());
+        options.delay_weight = 0.0;
+        assert!(options.check_weights().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+use crate::dataset::Dataset;
+use crate::metrics::{self, DelayBasis};
+use crate::project::{FundingYears, Project};
+use super::regional::Dimension;
+use super::{format_comma_float, truncate_name, ReportKind};
+
+pub const CSV_FILE: &str = "report_4_hierarchical_rollup.csv";
+
+// Nesting order, outermost first
+pub const LEVELS: [Dimension; 4] = [
+    Dimension::MainIsland,
+    Dimension::Region,
+    Dimension::Province,
+    Dimension::Municipality,
+];
+
+// Report 4 row: one node of the MainIsland → Region → Province → Municipality
+// tree, or the grand total
+#[derive(Clone, Debug)]
+pub struct RollupRow {
+    // None for the grand total
+    pub level: Option<Dimension>,
+    // Values from MainIsland down to this row's level; empty for the grand total
+    pub path: Vec<String>,
+    pub total_projects: usize,
+    pub total_budget: f64,
+    pub total_contract_cost: f64,
+    pub total_savings: f64,
+    pub median_savings: f64,
+    pub avg_delay: f64,
+    pub delay_basis: DelayBasis,
+}
+
+impl RollupRow {
+    pub fn level_label(&self) -> &'static str {
+        self.level.map_or("Total", |d| d.column())
+    }
+}
+
+// Report 4: Hierarchical Roll-up. Rows come in subtotal order: each node's
+// children first, then the node itself, with the grand total last.
+pub fn hierarchical_rollup(dataset: &Dataset) -> Vec<RollupRow> {
+    let items: Vec<&Project> = dataset.projects().iter().collect();
+    let mut rows = Vec::new();
+    roll_up(&items, &mut Vec::new(), &mut rows);
+    rows
+}
+
+fn roll_up(items: &[&Project], path: &mut Vec<String>, rows: &mut Vec<RollupRow>) {
+    let depth = path.len();
+    if let Some(dimension) = LEVELS.get(depth) {
+        let mut children: BTreeMap<String, Vec<&Project>> = BTreeMap::new();
+        for p in items {
+            children.entry(dimension.value(p)).or_default().push(p);
+        }
+        for (value, child_items) in children {
+            path.push(value);
+            roll_up(&child_items, path, rows);
+            path.pop();
+        }
+    }
+
+    let total_budget: f64 = items.iter().map(|p| p.approved_budget).sum();
+    let total_contract_cost: f64 = items.iter().map(|p| p.contract_cost).sum();
+
+    let savings: Vec<f64> = items.iter().map(|p| metrics::savings(p)).collect();
+    let delays: Vec<f64> = items.iter().map(|p| metrics::delay_days(p) as f64).collect();
+
+    rows.push(RollupRow {
+        level: depth.checked_sub(1).map(|i| LEVELS[i]),
+        path: path.clone(),
+        total_projects: items.len(),
+        total_budget,
+        total_contract_cost,
+        total_savings: savings.iter().sum(),
+        median_savings: metrics::median(&savings),
+        avg_delay: metrics::mean(&delays),
+        delay_basis: DelayBasis::of(items.iter().copied()),
+    });
+}
+
+pub fn print(rows: &[RollupRow], funding_years: &FundingYears) {
+    println!();
+    println!("{}", ReportKind::Rollup.title());
+    println!(
+        "(MainIsland → Region → Province → Municipality with subtotals; {} Projects)",
+        funding_years.label()
+    );
+    println!("({})", metrics::DELAY_NOTE);
+    println!();
+
+    println!(
+        "| {:<12} | {:<45} | {:>8} | {:>18} | {:>18} | {:>16} | {:>15} | {:>12} | {:<10} |",
+        "Level", "Name", "Projects", "TotalBudget", "ContractCost", "TotalSavings", "MedianSavings", "AvgDelayDays",
+        "DelayBasis"
+    );
+    println!("{}", "-".repeat(188));
+
+    for r in rows {
+        // Indent by depth so the nesting is visible
+        let name = match r.path.last() {
+            Some(value) => format!("{}{}", "  ".repeat(r.path.len() - 1), value.trim()),
+            None => "ALL PROJECTS".to_string(),
+        };
+        println!(
+            "| {:<12} | {:<45} | {:>8} | {:>18} | {:>18} | {:>16} | {:>15} | {:>12.2} | {:<10} |",
+            r.level_label(),
+            truncate_name(&name, 45),
+            r.total_projects,
+            format_comma_float(r.total_budget),
+            format_comma_float(r.total_contract_cost),
+            format_comma_float(r.total_savings),
+            format_comma_float(r.median_savings),
+            r.avg_delay,
+            r.delay_basis.label()
+        );
+    }
+}
+
+pub fn write_csv(rows: &[RollupRow], path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    let mut header = vec!["Level"];
+    header.extend(LEVELS.iter().map(|d| d.column()));
+    header.extend([
+        "TotalProjects",
+        "TotalBudget",
+        "TotalContractCost",
+        "TotalSavings",
+        "MedianSavings",
+        "AvgDelayDays",
+        "DelayBasis",
+    ]);
+    wtr.write_record(&header)?;
+
+    for r in rows {
+        // Columns below the row's level stay blank
+        let mut record = vec![r.level_label().to_string()];
+        record.extend((0..LEVELS.len()).map(|i| r.path.get(i).cloned().unwrap_or_default()));
+        record.extend([
+            r.total_projects.to_string(),
+            format!("{:.2}", r.total_budget),
+            format!("{:.2}", r.total_contract_cost),
+            format!("{:.2}", r.total_savings),
+            format!("{:.2}", r.median_savings),
+            format!("{:.2}", r.avg_delay),
+            r.delay_basis.label().to_string(),
+        ]);
+        wtr.write_record(&record)?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::collections::{BTreeMap, HashSet};
+use std::error::Error;
+use std::fs::File;
+use std::path::Path;
+use serde_json::{json, Value};
+use crate::dataset::Dataset;
+use crate::metrics;
+
+pub const JSON_FILE: &str = "summary.json";
+
+// How each figure is computed; written to summary.json next to the values
+const DEFINITIONS: [(&str, &str); 6] = [
+    (
+        "delay_days",
+        "max(0, ActualCompletionDate - planned completion) in days when PlannedCompletionDate or \
+         ContractDurationDays is known (slippage), otherwise max(0, ActualCompletionDate - StartDate) \
+         (duration), as in Reports 1-4",
+    ),
+    ("savings", "ApprovedBudgetForContract - ContractCost, as in Reports 1-4"),
+    ("global_avg_delay", "mean delay_days over all projects"),
+    ("total_provinces", "distinct non-blank Province values"),
+    ("municipalities", "distinct non-blank (Province, Municipality) pairs, since names repeat across provinces"),
+    ("district_engineering_offices", "distinct non-blank DistrictEngineeringOffice values"),
+];
+
+// Dataset-wide figures written to summary.json
+#[derive(Clone, Debug)]
+pub struct Summary {
+    pub funding_years: String,
+    pub total_projects: usize,
+    pub total_contractors: usize,
+    pub total_provinces: usize,
+    pub global_avg_delay: f64,
+    // Projects whose delay is slippage vs planned completion; the rest use duration
+    pub slippage_projects: usize,
+    pub total_savings: f64,
+    pub coverage: Coverage,
+}
+
+// Geographic spread of the dataset
+#[derive(Clone, Debug)]
+pub struct Coverage {
+    pub regions: usize,
+    pub municipalities: usize,
+    pub district_engineering_offices: usize,
+    // Projects whose Province column was blank, so not in total_provinces
+    pub projects_without_province: usize,
+    pub projects_by_main_island: BTreeMap<String, usize>,
+}
+
+pub fn summary(dataset: &Dataset) -> Summary {
+    let projects = dataset.projects();
+    let total_projects = projects.len();
+    let total_contractors = projects.iter().map(|p| p.contractor.clone()).collect::<HashSet<_>>().len();
+    let total_provinces = projects.iter().filter_map(|p| p.province.as_deref()).collect::<HashSet<_>>().len();
+
+    let total_savings: f64 = projects.iter().map(metrics::savings).sum();
+    let delays: Vec<f64> = projects.iter().map(|p| metrics::delay_days(p) as f64).collect();
+    let global_avg_delay = metrics::mean(&delays);
+    let slippage_projects = projects.iter().filter(|p| metrics::planned_completion_date(p).is_some()).count();
+
+    let mut projects_by_main_island: BTreeMap<String, usize> = BTreeMap::new();
+    for p in projects {
+        *projects_by_main_island.entry(p.main_island.clone()).or_default() += 1;
+    }
+    let coverage = Coverage {
+        regions: projects.iter().map(|p| p.region.as_str()).collect::<HashSet<_>>().len(),
+        municipalities: projects
+            .iter()
+            .filter_map(|p| Some((p.province.as_deref(), p.municipality.as_deref()?)))
+            .collect::<HashSet<_>>()
+            .len(),
+        district_engineering_offices: projects
+            .iter()
+            .filter_map(|p| p.district_engineering_office.as_deref())
+            .collect::<HashSet<_>>()
+            .len(),
+        projects_without_province: projects.iter().filter(|p| p.province.is_none()).count(),
+        projects_by_main_island,
+    };
+
+    Summary {
+        funding_years: dataset.funding_years().label(),
+        total_projects,
+        total_contractors,
+        total_provinces,
+        global_avg_delay,
+        slippage_projects,
+        total_savings,
+        coverage,
+    }
+}
+
+impl Summary {
+    pub fn to_json(&self) -> Value {
+        let definitions: serde_json::Map<String, Value> =
+            DEFINITIONS.iter().map(|(k, v)| (k.to_string(), json!(v))).collect();
+        json!({
+            "funding_years": self.funding_years,
+            "total_projects": self.total_projects,
+            "total_contractors": self.total_contractors,
+            "total_provinces": self.total_provinces,
+            "global_avg_delay": self.global_avg_delay,
+            "delay_basis": {
+                "slippage_projects": self.slippage_projects,
+                "duration_projects": self.total_projects - self.slippage_projects
+            },
+            "total_savings": self.total_savings,
+            "coverage": {
+                "regions": self.coverage.regions,
+                "provinces": self.total_provinces,
+                "municipalities": self.coverage.municipalities,
+                "district_engineering_offices": self.coverage.district_engineering_offices,
+                "projects_without_province": self.coverage.projects_without_province,
+                "projects_by_main_island": self.coverage.projects_by_main_island
+            },
+            "definitions": definitions
+        })
+    }
+
+    pub fn write_json(&self, path: &Path) -> Result<(), Box<dyn Error>> {
+        let file = File::create(path)?;
+        serde_json::to_writer_pretty(file, &self.to_json())?;
+        Ok(())
+    }
+}
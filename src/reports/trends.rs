@@ -0,0 +1,267 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::path::Path;
+use crate::dataset::Dataset;
+use crate::metrics;
+use crate::project::Project;
+use super::{format_comma_float, truncate_name};
+
+pub const CSV_FILE: &str = "report3_annual_trends.csv";
+
+// (FundingYear, AvgSavings)
+type YearAverage = (i32, f64);
+
+// Report 3 row: one (FundingYear, TypeOfWork) group
+#[derive(Clone, Debug)]
+pub struct TrendRow {
+    pub funding_year: i32,
+    pub type_of_work: String,
+    pub total_projects: usize,
+    pub avg_savings: f64,
+    pub overrun_rate: f64,
+    // Overrun = ContractCost - ApprovedBudget on projects where that is positive
+    pub total_overrun: f64,
+    // Median over the overrunning projects only; 0.0 when there are none
+    pub median_overrun: f64,
+    // Total overrun as a share of the overrunning projects' approved budget
+    pub overrun_pct_of_budget: f64,
+    // Largest single overrun; the id is None for rows without a ProjectId
+    pub worst_overrun: Option<WorstOverrun>,
+    // Change in AvgSavings vs the previous calendar year; None when that year
+    // has no rows for this work type or its average is zero
+    pub yoy_change: Option<f64>,
+    // Change vs the first year of the funding-year window, same rules
+    pub baseline_change: Option<f64>,
+    // Compound annual growth of AvgSavings from the work type's first to last
+    // year in the window; None with a single year or a non-positive endpoint
+    pub cagr: Option<f64>,
+}
+
+#[derive(Clone, Debug)]
+pub struct WorstOverrun {
+    pub project_id: Option<String>,
+    pub amount: f64,
+}
+
+// Report 3: Annual Project Type Cost Overrun Trends, sorted by year then
+// work type. Changes are percentages of the earlier value's magnitude, so a
+// rise from a negative average still reads as positive.
+pub fn annual_trends(dataset: &Dataset) -> Vec<TrendRow> {
+    let baseline_year = dataset.funding_years().baseline();
+
+    // Group by (FundingYear, TypeOfWork)
+    let mut grouped3: HashMap<(i32, String), Vec<&Project>> = HashMap::new();
+    for p in dataset.projects() {
+        grouped3
+            .entry((p.funding_year, p.type_of_work.clone()))
+            .or_default()
+            .push(p);
+    }
+
+    let mut rows3: Vec<TrendRow> = Vec::new();
+
+    // Average savings per (year, work type), for the change columns
+    let mut year_savings: HashMap<(i32, String), f64> = HashMap::new();
+    for (key, items) in &grouped3 {
+        let savings: Vec<f64> = items.iter().map(|p| metrics::savings(p)).collect();
+        year_savings.insert(key.clone(), metrics::mean(&savings));
+    }
+
+    // First and last (year, average) per work type, for CAGR
+    let mut endpoints: HashMap<&str, (YearAverage, YearAverage)> = HashMap::new();
+    for ((year, work_type), avg) in &year_savings {
+        let entry = endpoints.entry(work_type.as_str()).or_insert(((*year, *avg), (*year, *avg)));
+        if *year < entry.0.0 {
+            entry.0 = (*year, *avg);
+        }
+        if *year > entry.1.0 {
+            entry.1 = (*year, *avg);
+        }
+    }
+    let cagr_by_type: HashMap<String, Option<f64>> = endpoints
+        .into_iter()
+        .map(|(work_type, ((first_year, first), (last_year, last)))| {
+            let cagr = if last_year > first_year && first > 0.0 && last > 0.0 {
+                Some(((last / first).powf(1.0 / (last_year - first_year) as f64) - 1.0) * 100.0)
+            } else {
+                None
+            };
+            (work_type.to_string(), cagr)
+        })
+        .collect();
+
+    // Fill per (year, type_of_work)
+    for ((year, work_type), items) in grouped3 {
+        let total_projects = items.len();
+
+        let savings: Vec<f64> = items.iter().map(|p| metrics::savings(p)).collect();
+        let avg_savings = metrics::mean(&savings);
+        let overrun_rate = metrics::share_pct(&savings, |s| *s < 0.0);
+
+        let overruns: Vec<&Project> = items.iter().copied().filter(|p| metrics::overrun(p) > 0.0).collect();
+        let overrun_amounts: Vec<f64> = overruns.iter().map(|p| metrics::overrun(p)).collect();
+        // Folded from 0.0: an empty f64 sum() is -0.0, which prints as -0.00
+        let total_overrun = overrun_amounts.iter().fold(0.0, |sum, v| sum + v);
+        let median_overrun = metrics::median(&overrun_amounts);
+        let overrun_budget = overruns.iter().fold(0.0, |sum, p| sum + p.approved_budget);
+        let overrun_pct_of_budget = if overrun_budget > 0.0 {
+            total_overrun * 100.0 / overrun_budget
+        } else {
+            0.0
+        };
+        let worst_overrun = overruns
+            .iter()
+            .max_by(|a, b| metrics::overrun(a).total_cmp(&metrics::overrun(b)))
+            .map(|p| WorstOverrun {
+                project_id: p.project_id.clone(),
+                amount: metrics::overrun(p),
+            });
+
+        let previous = year_savings.get(&(year - 1, work_type.clone())).copied();
+        let yoy_change = previous.and_then(|prev| percent_change(prev, avg_savings));
+        let baseline = year_savings.get(&(baseline_year, work_type.clone())).copied();
+        let baseline_change = baseline.and_then(|base| percent_change(base, avg_savings));
+        let cagr = cagr_by_type.get(&work_type).copied().flatten();
+
+        rows3.push(TrendRow {
+            funding_year: year,
+            type_of_work: work_type,
+            total_projects,
+            avg_savings,
+            overrun_rate,
+            total_overrun,
+            median_overrun,
+            overrun_pct_of_budget,
+            worst_overrun,
+            yoy_change,
+            baseline_change,
+            cagr,
+        });
+    }
+
+    // Sort by year then work type
+    rows3.sort_by(|a, b| {
+        a.funding_year.cmp(&b.funding_year)
+            .then_with(|| a.type_of_work.cmp(&b.type_of_work))
+    });
+    rows3
+}
+
+fn percent_change(from: f64, to: f64) -> Option<f64> {
+    if from.abs() < f64::EPSILON {
+        None
+    } else {
+        Some((to - from) / from.abs() * 100.0)
+    }
+}
+
+// "n/a" for a missing value so it is never mistaken for 0.0
+fn format_optional(value: Option<f64>, decimals: usize, suffix: &str) -> String {
+    match value {
+        Some(v) => format!("{:.*}{}", decimals, v, suffix),
+        None => "n/a".to_string(),
+    }
+}
+
+// Blank when the group had no overruns
+fn worst_overrun_id(row: &TrendRow) -> String {
+    match &row.worst_overrun {
+        Some(worst) => worst.project_id.clone().unwrap_or_else(|| "(no id)".to_string()),
+        None => String::new(),
+    }
+}
+
+pub fn print(rows: &[TrendRow], baseline_year: i32) {
+    println!();
+    println!("Report 3: Annual Project Type Cost Overrun Trends");
+    println!(
+        "(Grouped by FundingYear and TypeOfWork; YoY vs previous year, baseline {}, CAGR over the window)",
+        baseline_year
+    );
+    println!();
+
+    // Print formatted table
+    println!(
+        "{:<12} | {:<40} | {:>15} | {:>15} | {:>12} | {:>16} | {:>14} | {:>14} | {:<16} | {:>12} | {:>14} | {:>12} |",
+        "FundingYear", "TypeOfWork", "TotalProjects", "AvgSavings", "OverrunRate", "TotalOverrun", "MedianOverrun",
+        "OverrunPctBudget", "WorstOverrunId", "YoYChange", "BaselineChange", "CAGR"
+    );
+    println!("{}", "-".repeat(224));
+
+    for r in rows {
+        let type_of_work_display = truncate_name(r.type_of_work.trim(), 40);
+
+        println!(
+            "| {:<12} | {:<40} | {:>15} | {:>15.2} | {:>11.1}% | {:>16} | {:>14} | {:>15.1}% | {:<16} | {:>12} | {:>14} | {:>12} |",
+            r.funding_year,
+            type_of_work_display,
+            r.total_projects,
+            r.avg_savings,
+            r.overrun_rate,
+            format_comma_float(r.total_overrun),
+            format_comma_float(r.median_overrun),
+            r.overrun_pct_of_budget,
+            truncate_name(&worst_overrun_id(r), 16),
+            format_optional(r.yoy_change, 1, "%"),
+            format_optional(r.baseline_change, 1, "%"),
+            format_optional(r.cagr, 1, "%")
+        );
+    }
+}
+
+pub fn write_csv(rows: &[TrendRow], path: &Path) -> Result<(), Box<dyn Error>> {
+    let mut wtr = csv::Writer::from_path(path)?;
+    wtr.write_record([
+        "FundingYear",
+        "TypeOfWork",
+        "TotalProjects",
+        "AvgSavings",
+        "OverrunRate",
+        "TotalOverrun",
+        "MedianOverrun",
+        "OverrunPctBudget",
+        "WorstOverrunId",
+        "WorstOverrunAmount",
+        "YoYChange",
+        "BaselineChange",
+        "CAGR",
+    ])?;
+    for r in rows {
+        wtr.write_record(&[
+            r.funding_year.to_string(),
+            r.type_of_work.clone(),
+            r.total_projects.to_string(),
+            format!("{:.2}", r.avg_savings),
+            format!("{:.2}", r.overrun_rate),
+            format!("{:.2}", r.total_overrun),
+            format!("{:.2}", r.median_overrun),
+            format!("{:.2}", r.overrun_pct_of_budget),
+            worst_overrun_id(r),
+            r.worst_overrun.as_ref().map_or(String::new(), |w| format!("{:.2}", w.amount)),
+            format_optional(r.yoy_change, 2, ""),
+            format_optional(r.baseline_change, 2, ""),
+            format_optional(r.cagr, 2, ""),
+        ])?;
+    }
+    wtr.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dataset::DuplicatePolicy;
+    use crate::project::{test_project, FundingYears};
+
+    #[test]
+    fn group_without_overruns_totals_positive_zero() {
+        let mut dataset = Dataset::default();
+        dataset.add_source("a", FundingYears::default(), vec![test_project()], DuplicatePolicy::KeepFirst);
+
+        let rows = annual_trends(&dataset);
+        assert_eq!(rows.len(), 1);
+        assert!(rows[0].total_overrun.is_sign_positive());
+        assert_eq!(format!("{:.2}", rows[0].total_overrun), "0.00");
+        assert!(rows[0].worst_overrun.is_none());
+    }
+}